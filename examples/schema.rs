@@ -3,8 +3,12 @@ use std::path::PathBuf;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
-use network_wars::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use network_wars::state::State;
+use network_wars::msg::{
+    AllowlistResponse, DividendsResponse, ExecuteMsg, FeesResponse, GamesResponse, InstantiateMsg, OwnershipResponse, 
+    PlayerResponse, PlayersResponse, PriceResponse, QueryMsg, ReferralsResponse, RolesResponse, RoundsResponse, 
+    SponsorsResponse, StatusResponse, SurplusResponse,
+};
+use network_wars::state::{Config, PendingConfig, RoundRecord, RoundState};
 
 fn main() {
     let mut out_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -15,5 +19,22 @@ fn main() {
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
-    export_schema(&schema_for!(State), &out_dir);
+    export_schema(&schema_for!(Config), &out_dir);
+    export_schema(&schema_for!(RoundState), &out_dir);
+    export_schema(&schema_for!(RoundRecord), &out_dir);
+    export_schema(&schema_for!(RoundsResponse), &out_dir);
+    export_schema(&schema_for!(PlayerResponse), &out_dir);
+    export_schema(&schema_for!(PlayersResponse), &out_dir);
+    export_schema(&schema_for!(DividendsResponse), &out_dir);
+    export_schema(&schema_for!(FeesResponse), &out_dir);
+    export_schema(&schema_for!(PriceResponse), &out_dir);
+    export_schema(&schema_for!(OwnershipResponse), &out_dir);
+    export_schema(&schema_for!(RolesResponse), &out_dir);
+    export_schema(&schema_for!(PendingConfig), &out_dir);
+    export_schema(&schema_for!(StatusResponse), &out_dir);
+    export_schema(&schema_for!(ReferralsResponse), &out_dir);
+    export_schema(&schema_for!(AllowlistResponse), &out_dir);
+    export_schema(&schema_for!(GamesResponse), &out_dir);
+    export_schema(&schema_for!(SurplusResponse), &out_dir);
+    export_schema(&schema_for!(SponsorsResponse), &out_dir);
 }
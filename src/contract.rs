@@ -1,25 +1,29 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    Addr, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, 
-    StdResult,
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
 };
 use cw2::{get_contract_version, set_contract_version};
+use semver::Version;
 
 use crate::execute::{
-    execute_claim, execute_configure, execute_deposit, execute_pause, execute_unlock_stale, 
-    execute_unpause,
+    create_game, execute_accept_ownership, execute_apply_config, execute_cancel_config, 
+    execute_cancel_ownership_proposal, execute_claim, execute_configure, execute_create_game, 
+    execute_deposit, execute_fund, execute_grant_role, execute_pause, execute_propose_owner, execute_receive, 
+    execute_renounce_ownership, execute_revoke_role, execute_set_referrer, execute_sweep, execute_unlock_stale, 
+    execute_unpause, execute_update_allowlist, execute_withdraw_dividends, execute_withdraw_fees, execute_withdraw_referral_earnings,
+    execute_withdraw_sponsorship,
+};
+use crate::migrate::{is_paused, run_migrations};
+use crate::query::{
+    query_allowlist, query_config, query_current_price, query_current_round, query_dividends, query_fees, query_games, 
+    query_ownership, query_pending_config, query_player, query_players, query_referrals, query_roles, query_round, 
+    query_rounds, query_sponsors, query_status, query_surplus,
 };
-use crate::query::{query_game};
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::state::{Archid, ARCHID, State, STATE};
+use crate::state::OWNER;
 use crate::error::ContractError;
 
-// Mainnet
-pub static DENOM: &str = "aarch";
-// Testnet
-// pub static DENOM: &str = "aconst";
-
 // version info for migration info
 const CONTRACT_NAME: &str = "network-wars";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -31,29 +35,14 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    let state = State {
-        owner: info.sender.clone(),
-        expiration: msg.expiration + env.block.time.seconds(),
-        min_deposit: msg.min_deposit,
-        last_deposit: env.block.time.seconds(),
-        last_depositor: info.sender.clone(),
-        extensions: msg.extensions,
-        stale: msg.stale,
-        reset_length: msg.reset_length,
-        round: 1_u64,
-        paused: None,
-    };
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    STATE.save(deps.storage, &state)?;
-
-    let registry = if msg.archid_registry.is_some() { msg.archid_registry } else { None };
-    let cw721 = if msg.archid_cw721.is_some() { msg.archid_cw721 } else { None };
-    let archid = Archid {registry, cw721};
-    ARCHID.save(deps.storage, &archid)?;
+    OWNER.save(deps.storage, &info.sender)?;
+    let game_id = create_game(deps, &env, &info, msg)?;
 
     Ok(Response::new()
         .add_attribute("action", "instantiate")
-        .add_attribute("owner", info.sender))
+        .add_attribute("owner", info.sender)
+        .add_attribute("game_id", game_id.to_string()))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -64,63 +53,132 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Deposit {} => execute_deposit(deps, env, info),
-        ExecuteMsg::Claim {} => execute_claim(deps, env, info),
-        ExecuteMsg::UnlockStale {} => execute_unlock_stale(deps, env, info),
+        ExecuteMsg::Deposit { game_id, quantity, referrer } => {
+            execute_deposit(deps, env, info, game_id, quantity, referrer)
+        }
+        ExecuteMsg::Receive(wrapper) => execute_receive(deps, env, info, wrapper),
+        ExecuteMsg::Claim { game_id } => execute_claim(deps, env, info, game_id),
+        ExecuteMsg::UnlockStale { game_id } => execute_unlock_stale(deps, env, info, game_id),
+        ExecuteMsg::WithdrawDividends { game_id } => execute_withdraw_dividends(deps, info, game_id),
+        ExecuteMsg::SetReferrer { game_id, referrer } => {
+            execute_set_referrer(deps, info, game_id, referrer)
+        }
+        ExecuteMsg::WithdrawReferralEarnings { game_id } => {
+            execute_withdraw_referral_earnings(deps, info, game_id)
+        }
         // Admin only
-        ExecuteMsg::Pause {} => execute_pause(deps, env, info),
-        ExecuteMsg::Unpause {} => execute_unpause(deps, env, info),
-        ExecuteMsg::Configure { msg } => execute_configure(deps, info, msg),
+        ExecuteMsg::CreateGame { config } => execute_create_game(deps, env, info, config),
+        ExecuteMsg::Pause { game_id } => execute_pause(deps, env, info, game_id),
+        ExecuteMsg::Unpause { game_id } => execute_unpause(deps, env, info, game_id),
+        ExecuteMsg::Configure { game_id, msg } => execute_configure(deps, env, info, game_id, msg),
+        ExecuteMsg::CancelConfig { game_id } => execute_cancel_config(deps, info, game_id),
+        ExecuteMsg::WithdrawFees { game_id } => execute_withdraw_fees(deps, info, game_id),
+        ExecuteMsg::Fund { game_id, round } => execute_fund(deps, env, info, game_id, round),
+        ExecuteMsg::WithdrawSponsorship { game_id, round } => {
+            execute_withdraw_sponsorship(deps, info, game_id, round)
+        }
+        ExecuteMsg::Sweep { asset, recipient } => execute_sweep(deps, env, info, asset, recipient),
+        ExecuteMsg::ProposeOwner { new_owner, expiry } => {
+            execute_propose_owner(deps, env, info, new_owner, expiry)
+        }
+        ExecuteMsg::CancelOwnershipProposal {} => execute_cancel_ownership_proposal(deps, info),
+        ExecuteMsg::RenounceOwnership {} => execute_renounce_ownership(deps, info),
+        ExecuteMsg::GrantRole { address, role } => execute_grant_role(deps, info, address, role),
+        ExecuteMsg::RevokeRole { address, role } => execute_revoke_role(deps, info, address, role),
+        ExecuteMsg::UpdateAllowlist { game_id, add, remove } => {
+            execute_update_allowlist(deps, info, game_id, add, remove)
+        }
+        // Pending owner only
+        ExecuteMsg::AcceptOwnership {} => execute_accept_ownership(deps, env, info),
+        // Anyone once the config delay has passed
+        ExecuteMsg::ApplyConfig { game_id } => execute_apply_config(deps, env, game_id),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::Game {} => to_binary(&query_game(deps)?),
+        QueryMsg::Games { start_after, limit } => to_binary(&query_games(deps, start_after, limit)?),
+        QueryMsg::Config { game_id } => to_binary(&query_config(deps, game_id)?),
+        QueryMsg::CurrentRound { game_id } => to_binary(&query_current_round(deps, game_id)?),
+        QueryMsg::Status { game_id } => to_binary(&query_status(deps, env, game_id)?),
+        QueryMsg::Round { game_id, round } => to_binary(&query_round(deps, game_id, round)?),
+        QueryMsg::Rounds { game_id, start_after, limit } => {
+            to_binary(&query_rounds(deps, game_id, start_after, limit)?)
+        }
+        QueryMsg::Player { game_id, address, round } => {
+            to_binary(&query_player(deps, game_id, address, round)?)
+        }
+        QueryMsg::Players { game_id, round, start_after, limit } => {
+            to_binary(&query_players(deps, game_id, round, start_after, limit)?)
+        }
+        QueryMsg::Dividends { game_id, address } => {
+            to_binary(&query_dividends(deps, game_id, address)?)
+        }
+        QueryMsg::Fees { game_id } => to_binary(&query_fees(deps, game_id)?),
+        QueryMsg::CurrentPrice { game_id } => to_binary(&query_current_price(deps, game_id)?),
+        QueryMsg::Ownership {} => to_binary(&query_ownership(deps)?),
+        QueryMsg::Roles { start_after, limit } => {
+            to_binary(&query_roles(deps, start_after, limit)?)
+        }
+        QueryMsg::PendingConfig { game_id } => to_binary(&query_pending_config(deps, game_id)?),
+        QueryMsg::Referrals { game_id, address } => {
+            to_binary(&query_referrals(deps, game_id, address)?)
+        }
+        QueryMsg::Allowlist { game_id, start_after, limit } => {
+            to_binary(&query_allowlist(deps, game_id, start_after, limit)?)
+        }
+        QueryMsg::Surplus { asset } => to_binary(&query_surplus(deps, env, asset)?),
+        QueryMsg::Sponsors { game_id, round, start_after, limit } => {
+            to_binary(&query_sponsors(deps, game_id, round, start_after, limit)?)
+        }
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    // Game play must be paused for upgrade
-    // let state = STATE.load(deps.storage)?;
-    // if !state.is_paused() {
-    //     return Err(ContractError::Unauthorized {});
-    // }
-
+pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
     let original_version = get_contract_version(deps.storage)?;
-    let name = CONTRACT_NAME.to_string();
-    let version = CONTRACT_VERSION.to_string();
-    if original_version.contract != name {
-        return Err(ContractError::InvalidInput {});
+    if original_version.contract != CONTRACT_NAME {
+        return Err(ContractError::CannotMigrateContract { 
+            contract: original_version.contract,
+        });
     }
-    if original_version.version >= version {
-        return Err(ContractError::InvalidInput {});
+
+    // Only upgrades are allowed
+    let from = Version::parse(&original_version.version)?;
+    let to = Version::parse(CONTRACT_VERSION)?;
+    if from >= to {
+        return Err(ContractError::CannotMigrateVersion {
+            from: original_version.version,
+            to: CONTRACT_VERSION.to_string(),
+        });
     }
 
-    // Add state migration for ArchID contracts
-    // (1 time migration)
-    let registry: Addr = deps.api.addr_validate("archway1275jwjpktae4y4y0cdq274a2m0jnpekhttnfuljm6n59wnpyd62qppqxq0")?;
-    let cw721: Addr = deps.api.addr_validate("archway1cf5rq0amcl5m2flqrtl4gw2mdl3zdec9vlp5hfa9hgxlwnmrlazsdycu4l")?;
-    let archid = Archid {
-        registry: Some(registry),
-        cw721: Some(cw721),
-    };
-    ARCHID.save(deps.storage, &archid)?;
-    // End state migrations
+    // Game play can be required to be paused for upgrade
+    if msg.require_paused.unwrap_or(false) && !is_paused(deps.storage)? {
+        return Err(ContractError::NotPaused {});
+    }
+
+    let applied = run_migrations(deps.branch(), &env, &from, &msg)?;
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    Ok(Response::default())
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", original_version.version)
+        .add_attribute("to_version", CONTRACT_VERSION)
+        .add_attribute("migrations", applied.join(",")))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use cosmwasm_std::testing::{
-        mock_dependencies, mock_env, mock_info,
+        mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info,
+    };
+    use cosmwasm_std::{coins, Addr, Uint128};
+    use crate::state::{
+        Archid, AssetInfo, CONFIGS, CURRENT_ROUNDS, EligibilityRule, LEGACY_ARCHID, LEGACY_STATE, LegacyState, POTS,
     };
-    use cosmwasm_std::{coins, Uint128};
 
     #[test]
     fn can_instantiate() {
@@ -138,20 +196,86 @@ mod tests {
         assert_eq!("creator", owner);
     }
 
+    #[test]
+    fn migrates_legacy_state() {
+        let mut deps = mock_dependencies_with_balance(&coins(5000000, "aarch"));
+        set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "0.1.4").unwrap();
+
+        let owner = Addr::unchecked("creator");
+        let cw721 = Addr::unchecked("archid_cw721");
+        let legacy = LegacyState {
+            owner: owner.clone(),
+            expiration: 2000,
+            min_deposit: Uint128::from(1000000u128),
+            last_deposit: 1500,
+            last_depositor: Addr::unchecked("depositor"),
+            extensions: 1000,
+            stale: 5000,
+            reset_length: 5000,
+            round: 3,
+            paused: None,
+        };
+        LEGACY_STATE.save(deps.as_mut().storage, &legacy).unwrap();
+        let archid = Archid {
+            registry: Some(Addr::unchecked("archid_registry")),
+            cw721: Some(cw721.clone()),
+        };
+        LEGACY_ARCHID.save(deps.as_mut().storage, &archid).unwrap();
+
+        let msg = MigrateMsg {
+            archid_registry: None,
+            archid_cw721: None,
+            legacy_denom: Some("aarch".to_string()),
+            require_paused: None,
+        };
+        migrate(deps.as_mut(), mock_env(), msg).unwrap();
+
+        // Game becomes game 1, with ArchID folded into its config
+        assert_eq!(OWNER.load(deps.as_ref().storage).unwrap(), owner);
+        let config = CONFIGS.load(deps.as_ref().storage, 1).unwrap();
+        assert_eq!(config.min_deposit, Uint128::from(1000000u128));
+        assert_eq!(config.eligibility, EligibilityRule::ArchId { cw721 });
+
+        let state = CURRENT_ROUNDS.load(deps.as_ref().storage, 1).unwrap();
+        assert_eq!(state.round, 3);
+        assert_eq!(state.last_depositor, Some(Addr::unchecked("depositor")));
+        assert_eq!(state.expiration, 2000);
+        assert_eq!(state.round_start, mock_env().block.time.seconds());
+
+        // Contract balance becomes the game's pot
+        let pot = POTS.load(deps.as_ref().storage, 1).unwrap();
+        assert_eq!(pot, Uint128::from(5000000u128));
+
+        // Old items are removed
+        assert!(LEGACY_STATE.may_load(deps.as_ref().storage).unwrap().is_none());
+        assert!(LEGACY_ARCHID.may_load(deps.as_ref().storage).unwrap().is_none());
+    }
+
     fn instantiate_contract(deps: DepsMut) -> Response {
-        let env = mock_env();
         let extends: u64 = 1000;
         let reset: u64 = extends * 5;
-        let expires: u64 = env.block.time.seconds() + extends.clone();
+        let expires: u64 = extends;
         
         let msg = InstantiateMsg {
+            asset: AssetInfo::Native { denom: "aarch".to_string() },
             archid_registry: None,
-            archid_cw721: None,
             expiration: expires,
             min_deposit: Uint128::from(1000000u128),
             extensions: extends,
             stale: reset.clone(),
             reset_length: reset,
+            payout: None,
+            fee_bps: None,
+            fee_recipient: None,
+            price_curve: None,
+            max_remaining: None,
+            max_round_duration: None,
+            funds_policy: None,
+            config_delay: None,
+            referral_bps: None,
+            unlock_reward_bps: None,
+            keeper_bounty: None,
+            eligibility: None,
         };
         let info = mock_info("creator", &coins(1000, "token"));
         instantiate(deps, mock_env(), info, msg).unwrap()
@@ -1,4 +1,4 @@
-use cosmwasm_std::{Coin, StdError};
+use cosmwasm_std::{Coin, StdError, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,26 +6,154 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Semver(#[from] semver::Error),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
     #[error("Invalid input")]
     InvalidInput {},
 
+    #[error("{field} must be greater than zero")]
+    ZeroValue {
+        field: String,
+    },
+
+    #[error("{field} cannot be less than {min}")]
+    ValueTooSmall {
+        field: String,
+        min: Uint128,
+    },
+
+    #[error("{field} cannot be greater than {max}")]
+    ValueTooLarge {
+        field: String,
+        max: u64,
+    },
+
+    #[error("{field} is required")]
+    MissingValue {
+        field: String,
+    },
+
     #[error("Insufficient funds")]
     InsufficientFunds {
         required: Option<Coin>,
     },
 
+    #[error("Deposit quantity must be at least 1")]
+    InvalidQuantity {},
+
+    #[error("Sent {sent} but deposit costs {expected}")]
+    IncorrectPayment {
+        expected: Uint128,
+        sent: Uint128,
+    },
+
+    #[error("No ownership transfer is pending")]
+    NoPendingOwner {},
+
+    #[error("Ownership proposal has expired")]
+    OwnershipProposalExpired {},
+
+    #[error("Ownership can only be transferred with ProposeOwner")]
+    OwnerRoleNotGrantable {},
+
+    #[error("No configuration change is pending")]
+    NoPendingConfig {},
+
+    #[error("A configuration change is already pending")]
+    ConfigPending {},
+
+    #[error("Pending configuration is not effective yet")]
+    ConfigNotEffective {},
+
+    #[error("Deposits can't include {denom}")]
+    UnexpectedDenom {
+        denom: String,
+    },
+
     #[error("Players must own an ArchID")]
     NoArchid {},
+
+    #[error("Players must own an ArchID that has not expired")]
+    ExpiredArchid {},
+
+    #[error("Player does not meet the game's eligibility rules")]
+    NotEligible {},
+
+    #[error("Game {game_id} does not exist")]
+    GameNotFound { game_id: u64 },
     
     #[error("Gameplay can resume when winner claims prize or it becomes stale")]
     Gameover {},
 
+    #[error("Round {round} has already ended")]
+    RoundEnded { round: u64 },
+
+    #[error("Round {round} has already started")]
+    RoundStarted { round: u64 },
+
+    #[error("Prize can no longer be claimed, the round is stale")]
+    ClaimExpired {},
+
     #[error("Game must be stale")]
     NotStale {},
 
     #[error("Game play is paused")]
     Paused {},
+
+    #[error("Payout split must add up to 10000 basis points")]
+    InvalidPayoutSplit {},
+
+    #[error("No dividends to withdraw")]
+    NoDividends {},
+
+    #[error("Fee cannot exceed 10000 basis points")]
+    InvalidFee {},
+
+    #[error("No fees to withdraw")]
+    NoFees {},
+
+    #[error("No sponsorship to withdraw")]
+    NoSponsorship {},
+
+    #[error("No surplus to sweep")]
+    NoSurplus {},
+
+    #[error("Game is not played with this asset")]
+    InvalidAsset {},
+
+    #[error("Price steps must be ordered by deposit count")]
+    InvalidPriceCurve {},
+
+    #[error("Round has reached its maximum duration")]
+    RoundDurationExceeded {},
+
+    #[error("Players can't refer themselves")]
+    SelfReferral {},
+
+    #[error("Referrer must have made a deposit")]
+    InvalidReferrer {},
+
+    #[error("Referrer is already registered")]
+    ReferrerAlreadySet {},
+
+    #[error("No referral earnings to withdraw")]
+    NoReferralEarnings {},
+
+    #[error("Cannot migrate from contract {contract}")]
+    CannotMigrateContract {
+        contract: String,
+    },
+
+    #[error("Cannot migrate from version {from} to {to}")]
+    CannotMigrateVersion {
+        from: String,
+        to: String,
+    },
+
+    #[error("Game play must be paused for migration")]
+    NotPaused {},
 }
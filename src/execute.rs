@@ -1,26 +1,459 @@
 use cosmwasm_std::{
-    BankMsg, CosmosMsg, Coin, DepsMut, Env, MessageInfo, QueryRequest, Response,
-    to_binary, WasmQuery
+    Addr, Api, BankMsg, BlockInfo, CosmosMsg, Coin, Deps, DepsMut, Empty, Env, from_binary, MessageInfo, QueryRequest, 
+    Response, StdResult, Storage, to_binary, Uint128, WasmQuery
 };
+use cw20::Cw20ReceiveMsg;
+use cw4::{Cw4QueryMsg, MemberResponse};
 
-// use archid_registry::msg::{QueryMsg as QueryMsgArchid, ResolveAddressResponse};
-use cw721::TokensResponse;
+use cw721::{NftInfoResponse, TokensResponse};
 use archid_token::{
     QueryMsg as Cw721QueryMsg, Extension,
 };
 
-use crate::contract::DENOM;
-use crate::msg::{ConfigureMsg};
-use crate::state::{Archid, ARCHID, State, STATE};
+use crate::msg::{ConfigureMsg, InstantiateMsg, ReceiveMsg};
+use crate::state::{
+    accounted_balance, ALLOWLIST, AssetInfo, BPS_DENOMINATOR, Config, CONFIGS, CURRENT_ROUNDS, DIVIDEND_RESERVES, 
+    EligibilityRule, FEES, FundsPolicy, GAME_COUNT, GameConfig, has_role, is_owner, load_game, MAX_FUND_AHEAD, OWNER, 
+    pending_dividends, PendingConfig, PENDING_CONFIGS, PendingOwner, PENDING_OWNER, PLAYER_ROUNDS, PLAYERS, POTS, REFERRAL_EARNINGS, 
+    REFERRAL_RESERVES, REFERRERS, Role, ROLES, ROUND_PLAYERS, resolve_archid, RoundOutcome, RoundRecord, ROUNDS, 
+    RoundState, SPONSOR_RESERVES, SPONSORED, SPONSORS, validate_bps, validate_duration, validate_limit,
+};
 use crate::error::ContractError;
 
+// Tokens requested per page when checking ArchID ownership
+const ARCHID_PAGE_LIMIT: u32 = 30;
+
+// Validate and store a new game, returning its id
+pub fn create_game(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<u64, ContractError> {
+    let payout = msg.payout.unwrap_or_default();
+    if !payout.is_valid() {
+        return Err(ContractError::InvalidPayoutSplit {});
+    }
+    // Fees and referral rewards are both taken from deposits
+    let fee_bps = msg.fee_bps.unwrap_or_default();
+    let referral_bps = msg.referral_bps.unwrap_or_default();
+    if fee_bps + referral_bps > BPS_DENOMINATOR {
+        return Err(ContractError::InvalidFee {});
+    }
+    let unlock_reward_bps = msg.unlock_reward_bps.unwrap_or_default();
+    validate_bps("unlock_reward_bps", unlock_reward_bps)?;
+    let keeper_bounty = msg.keeper_bounty.unwrap_or_default();
+    let asset = match msg.asset {
+        AssetInfo::Native { denom } => AssetInfo::Native { denom },
+        AssetInfo::Cw20 { address } => AssetInfo::Cw20 { 
+            address: deps.api.addr_validate(address.as_str())?,
+        },
+    };
+    let game_config = GameConfig {
+        min_deposit: msg.min_deposit,
+        extensions: msg.extensions,
+        stale: msg.stale,
+        reset_length: msg.reset_length,
+    };
+    game_config.validate()?;
+    validate_duration("expiration", msg.expiration)?;
+    let price_curve = msg.price_curve.unwrap_or_default();
+    price_curve.validate(msg.min_deposit)?;
+
+    let config = Config {
+        asset,
+        min_deposit: msg.min_deposit,
+        extensions: msg.extensions,
+        stale: msg.stale,
+        reset_length: msg.reset_length,
+        payout,
+        fee_bps,
+        fee_recipient: msg.fee_recipient
+            .map(|fee_recipient| deps.api.addr_validate(&fee_recipient))
+            .transpose()?,
+        price_curve,
+        max_remaining: validate_limit("max_remaining", msg.max_remaining.unwrap_or_default())?,
+        max_round_duration: validate_limit("max_round_duration", msg.max_round_duration.unwrap_or_default())?,
+        funds_policy: msg.funds_policy.unwrap_or_default(),
+        config_delay: validate_limit("config_delay", msg.config_delay.unwrap_or_default())?.unwrap_or_default(),
+        archid_registry: msg.archid_registry
+            .map(|registry| deps.api.addr_validate(&registry))
+            .transpose()?,
+        referral_bps,
+        eligibility: msg.eligibility
+            .map(|eligibility| eligibility.validate(deps.api))
+            .transpose()?
+            .unwrap_or_default(),
+        unlock_reward_bps,
+        keeper_bounty,
+    };
+    let state = RoundState {
+        round: 1_u64,
+        expiration: msg.expiration + env.block.time.seconds(),
+        last_deposit: env.block.time.seconds(),
+        last_depositor: None,
+        paused: None,
+        round_start: env.block.time.seconds(),
+        deposits: 0,
+        deposited: Uint128::zero(),
+        last_depositor_name: None,
+        carry_over: Uint128::zero(),
+    };
+
+    // Funds sent in the game's asset sponsor its first round
+    let pot = config.asset.sent_amount(&info.funds);
+
+    let game_id = GAME_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    GAME_COUNT.save(deps.storage, &game_id)?;
+    CONFIGS.save(deps.storage, game_id, &config)?;
+    CURRENT_ROUNDS.save(deps.storage, game_id, &state)?;
+    POTS.save(deps.storage, game_id, &pot)?;
+    if !pot.is_zero() {
+        record_sponsor(deps.storage, game_id, state.round, &info.sender, pot)?;
+    }
+    Ok(game_id)
+}
+
+// Host another game in this contract (admin only)
+pub fn execute_create_game(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    // Only configurators can create games
+    if !has_role(deps.storage, &info.sender, Role::Configurator)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let game_id = create_game(deps, &env, &info, msg)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_create_game")
+        .add_attribute("game_id", game_id.to_string()))
+}
+
 pub fn execute_deposit(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    game_id: u64,
+    quantity: Option<u32>,
+    referrer: Option<String>,
 ) -> Result<Response, ContractError> {
-    let mut state = STATE.load(deps.storage)?;
+    let (config, state) = load_game(deps.storage, game_id)?;
+
+    // Game must be played with native funds
+    let denom = match &config.asset {
+        AssetInfo::Native { denom } => denom.clone(),
+        AssetInfo::Cw20 { .. } => return Err(ContractError::InvalidAsset {}),
+    };
+
+    check_can_deposit(deps.as_ref(), &env, game_id, &config, &state, &info.sender)?;
+    let quantity = check_quantity(quantity)?;
+    let referrer = check_referrer(deps.as_ref(), game_id, &info.sender, referrer)?;
+
+    // Sender must have sent correct funds
+    let cost = config.price_for(&state, quantity)?;
+    let required_payment = Coin {
+        denom: denom.clone(),
+        amount: cost,
+    };
+    check_sent_required_payment(&info.funds, Some(required_payment))?;
+
+    // Funds sent beyond the deposit price
+    let excess: Vec<Coin> = info.funds
+        .iter()
+        .map(|coin| {
+            if coin.denom == denom {
+                Coin { denom: coin.denom.clone(), amount: coin.amount - cost }
+            } else {
+                coin.clone()
+            }
+        })
+        .filter(|coin| !coin.amount.is_zero())
+        .collect();
+
+    if config.funds_policy == FundsPolicy::Reject {
+        if let Some(coin) = excess.iter().find(|coin| coin.denom != denom) {
+            return Err(ContractError::UnexpectedDenom { denom: coin.denom.clone() });
+        }
+        if let Some(coin) = excess.first() {
+            return Err(ContractError::IncorrectPayment {
+                expected: cost,
+                sent: cost + coin.amount,
+            });
+        }
+    }
+
+    let res = deposit(deps, env, game_id, &config, state, info.sender.clone(), referrer, quantity, cost)?;
+
+    // Refund excess funds
+    if excess.is_empty() {
+        return Ok(res);
+    }
+    let refund = excess
+        .iter()
+        .map(|coin| coin.to_string())
+        .collect::<Vec<String>>()
+        .join(",");
+    let bank_transfer_msg = BankMsg::Send {
+        to_address: info.sender.to_string(),
+        amount: excess,
+    };
+    Ok(res
+        .add_message(bank_transfer_msg)
+        .add_attribute("refund", refund))
+}
+
+// Cw20 deposits
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let depositor = deps.api.addr_validate(&wrapper.sender)?;
+    let msg: ReceiveMsg = from_binary(&wrapper.msg)?;
+    match msg {
+        ReceiveMsg::Deposit { game_id, quantity, referrer } => {
+            let (config, state) = load_game(deps.storage, game_id)?;
+
+            // Game must be played with the sending token
+            match &config.asset {
+                AssetInfo::Cw20 { address } if *address == info.sender => {}
+                _ => return Err(ContractError::InvalidAsset {}),
+            }
+
+            check_can_deposit(deps.as_ref(), &env, game_id, &config, &state, &depositor)?;
+            let quantity = check_quantity(quantity)?;
+            let referrer = check_referrer(deps.as_ref(), game_id, &depositor, referrer)?;
+
+            // Sender must have sent correct funds
+            let cost = config.price_for(&state, quantity)?;
+            if wrapper.amount < cost {
+                return Err(ContractError::InsufficientFunds { required: None });
+            }
+            let excess = wrapper.amount - cost;
+            if config.funds_policy == FundsPolicy::Reject && !excess.is_zero() {
+                return Err(ContractError::IncorrectPayment {
+                    expected: cost,
+                    sent: wrapper.amount,
+                });
+            }
+
+            let refund_msg = config.asset.transfer_msg(&depositor, excess)?;
+            let res = deposit(deps, env, game_id, &config, state, depositor, referrer, quantity, cost)?;
+
+            // Refund excess tokens
+            if excess.is_zero() {
+                return Ok(res);
+            }
+            Ok(res
+                .add_message(refund_msg)
+                .add_attribute("refund", excess))
+        }
+        ReceiveMsg::Fund { game_id, round } => {
+            let (config, state) = load_game(deps.storage, game_id)?;
+
+            // Game must be played with the sending token
+            match &config.asset {
+                AssetInfo::Cw20 { address } if *address == info.sender => {}
+                _ => return Err(ContractError::InvalidAsset {}),
+            }
+
+            fund(deps.storage, &env, game_id, &state, &depositor, round, wrapper.amount)
+        }
+    }
+}
+
+// Sponsor the current round, or a future one
+pub fn execute_fund(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    game_id: u64,
+    round: Option<u64>,
+) -> Result<Response, ContractError> {
+    let (config, state) = load_game(deps.storage, game_id)?;
+
+    // Game must be played with native funds
+    let denom = match &config.asset {
+        AssetInfo::Native { denom } => denom.clone(),
+        AssetInfo::Cw20 { .. } => return Err(ContractError::InvalidAsset {}),
+    };
+    if let Some(coin) = info.funds.iter().find(|coin| coin.denom != denom) {
+        return Err(ContractError::UnexpectedDenom { denom: coin.denom.clone() });
+    }
+    let amount = config.asset.sent_amount(&info.funds);
+
+    fund(deps.storage, &env, game_id, &state, &info.sender, round, amount)
+}
+
+// Sponsor funds go to the pot, or are held until their round
+// starts, the round's timer and leader are left unchanged
+fn fund(
+    storage: &mut dyn Storage,
+    env: &Env,
+    game_id: u64,
+    state: &RoundState,
+    sponsor: &Addr,
+    round: Option<u64>,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::InsufficientFunds { required: None });
+    }
+    let round = round.unwrap_or(state.round);
+    if round < state.round {
+        return Err(ContractError::RoundEnded { round });
+    }
+    if round > state.round + MAX_FUND_AHEAD {
+        return Err(ContractError::ValueTooLarge {
+            field: "round".to_string(),
+            max: state.round + MAX_FUND_AHEAD,
+        });
+    }
+
+    if round == state.round {
+        // Current round must still be running
+        if state.is_expired(&env.block) {
+            return Err(ContractError::Gameover {});
+        }
+        let pot = POTS.may_load(storage, game_id)?.unwrap_or_default();
+        POTS.save(storage, game_id, &(pot + amount))?;
+    } else {
+        let reserved = SPONSOR_RESERVES.may_load(storage, game_id)?.unwrap_or_default();
+        SPONSOR_RESERVES.save(storage, game_id, &(reserved + amount))?;
+    }
+    record_sponsor(storage, game_id, round, sponsor, amount)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_fund")
+        .add_attribute("game_id", game_id.to_string())
+        .add_attribute("round", round.to_string())
+        .add_attribute("sponsor", sponsor)
+        .add_attribute("amount", amount))
+}
+
+fn record_sponsor(
+    storage: &mut dyn Storage,
+    game_id: u64,
+    round: u64,
+    sponsor: &Addr,
+    amount: Uint128,
+) -> StdResult<()> {
+    let funded = SPONSORS.may_load(storage, (game_id, round, sponsor))?.unwrap_or_default();
+    SPONSORS.save(storage, (game_id, round, sponsor), &(funded + amount))?;
+    let total = SPONSORED.may_load(storage, (game_id, round))?.unwrap_or_default();
+    SPONSORED.save(storage, (game_id, round), &(total + amount))?;
+    Ok(())
+}
+
+// Refund a sponsorship of a round that hasn't started
+pub fn execute_withdraw_sponsorship(
+    deps: DepsMut,
+    info: MessageInfo,
+    game_id: u64,
+    round: u64,
+) -> Result<Response, ContractError> {
+    let (config, state) = load_game(deps.storage, game_id)?;
+    if round <= state.round {
+        return Err(ContractError::RoundStarted { round });
+    }
+
+    let amount = SPONSORS.may_load(deps.storage, (game_id, round, &info.sender))?.unwrap_or_default();
+    if amount.is_zero() {
+        return Err(ContractError::NoSponsorship {});
+    }
+    SPONSORS.remove(deps.storage, (game_id, round, &info.sender));
+    let total = SPONSORED.load(deps.storage, (game_id, round))?;
+    if total == amount {
+        SPONSORED.remove(deps.storage, (game_id, round));
+    } else {
+        SPONSORED.save(deps.storage, (game_id, round), &(total - amount))?;
+    }
+    let reserved = SPONSOR_RESERVES.load(deps.storage, game_id)?;
+    SPONSOR_RESERVES.save(deps.storage, game_id, &(reserved - amount))?;
+
+    let transfer: CosmosMsg = config.asset.transfer_msg(&info.sender, amount)?;
 
+    Ok(Response::new()
+        .add_attribute("action", "execute_withdraw_sponsorship")
+        .add_attribute("game_id", game_id.to_string())
+        .add_attribute("round", round.to_string())
+        .add_attribute("sponsor", info.sender)
+        .add_attribute("amount", amount)
+        .add_message(transfer))
+}
+
+// Move funds sponsored ahead of time into the pot of
+// the round being started, returning the amount
+fn start_sponsored_round(
+    storage: &mut dyn Storage,
+    game_id: u64,
+    round: u64,
+) -> StdResult<Uint128> {
+    let sponsored = SPONSORED.may_load(storage, (game_id, round))?.unwrap_or_default();
+    if sponsored.is_zero() {
+        return Ok(sponsored);
+    }
+    let reserved = SPONSOR_RESERVES.may_load(storage, game_id)?.unwrap_or_default();
+    SPONSOR_RESERVES.save(storage, game_id, &(reserved - sponsored))?;
+    let pot = POTS.may_load(storage, game_id)?.unwrap_or_default();
+    POTS.save(storage, game_id, &(pot + sponsored))?;
+    Ok(sponsored)
+}
+
+// Deposits default to a single unit
+fn check_quantity(quantity: Option<u32>) -> Result<u64, ContractError> {
+    match quantity {
+        Some(0) => Err(ContractError::InvalidQuantity {}),
+        Some(quantity) => Ok(quantity as u64),
+        None => Ok(1),
+    }
+}
+
+// Registered referrers take precedence over the one sent with a deposit
+fn check_referrer(
+    deps: Deps,
+    game_id: u64,
+    depositor: &Addr,
+    referrer: Option<String>,
+) -> Result<Option<Addr>, ContractError> {
+    if let Some(registered) = REFERRERS.may_load(deps.storage, (game_id, depositor))? {
+        return Ok(Some(registered));
+    }
+    referrer
+        .map(|referrer| validate_referrer(deps, game_id, depositor, &referrer))
+        .transpose()
+}
+
+// Referrers must be other players who have made a deposit
+fn validate_referrer(
+    deps: Deps,
+    game_id: u64,
+    player: &Addr,
+    referrer: &str,
+) -> Result<Addr, ContractError> {
+    let referrer = deps.api.addr_validate(referrer)?;
+    if referrer == *player {
+        return Err(ContractError::SelfReferral {});
+    }
+    if !PLAYERS.has(deps.storage, (game_id, &referrer)) {
+        return Err(ContractError::InvalidReferrer {});
+    }
+    Ok(referrer)
+}
+
+fn check_can_deposit(
+    deps: Deps,
+    env: &Env,
+    game_id: u64,
+    config: &Config,
+    state: &RoundState,
+    depositor: &Addr,
+) -> Result<(), ContractError> {
     // Game play must not be paused for upgrades
     if state.is_paused() {
         return Err(ContractError::Paused {});
@@ -31,50 +464,222 @@ pub fn execute_deposit(
         return Err(ContractError::Gameover {});
     }
 
-    // Sender should own an ArchID
-    let archid = ARCHID.load(deps.storage)?;
-    if let Some(contract_addr) = archid.cw721 {
+    // Round must not have run past its hard cap
+    if state.is_past_max_duration(&env.block, config) {
+        return Err(ContractError::RoundDurationExceeded {});
+    }
+
+    // Sender must pass the game's eligibility rule
+    check_eligibility(deps, env, game_id, &config.eligibility, depositor)?;
+
+    Ok(())
+}
+
+fn check_eligibility(
+    deps: Deps,
+    env: &Env,
+    game_id: u64,
+    rule: &EligibilityRule,
+    depositor: &Addr,
+) -> Result<(), ContractError> {
+    match rule {
+        EligibilityRule::None {} => Ok(()),
+        EligibilityRule::Cw721Holder { contract } => {
+            let query_msg: archid_token::QueryMsg<Extension> = Cw721QueryMsg::Tokens {
+                owner: depositor.clone().into(),
+                start_after: None,
+                limit: Some(1),
+            };
+            let query_resp: TokensResponse = deps.querier.query_wasm_smart(contract, &query_msg)?;
+            if query_resp.tokens.is_empty() {
+                return Err(ContractError::NotEligible {});
+            }
+            Ok(())
+        }
+        EligibilityRule::ArchId { cw721 } => check_archid(deps, env, cw721, depositor),
+        EligibilityRule::Cw4Member { group } => {
+            let query_msg = Cw4QueryMsg::Member {
+                addr: depositor.to_string(),
+                at_height: None,
+            };
+            let query_resp: MemberResponse = deps.querier.query_wasm_smart(group, &query_msg)?;
+            if query_resp.weight.is_none() {
+                return Err(ContractError::NotEligible {});
+            }
+            Ok(())
+        }
+        EligibilityRule::Allowlist {} => {
+            if !ALLOWLIST.has(deps.storage, (game_id, depositor)) {
+                return Err(ContractError::NotEligible {});
+            }
+            Ok(())
+        }
+        EligibilityRule::Cw20MinBalance { token, amount } => {
+            let asset = AssetInfo::Cw20 { address: token.clone() };
+            if asset.query_balance(&deps.querier, depositor)? < *amount {
+                return Err(ContractError::NotEligible {});
+            }
+            Ok(())
+        }
+        EligibilityRule::And { rules } => {
+            for rule in rules {
+                check_eligibility(deps, env, game_id, rule, depositor)?;
+            }
+            Ok(())
+        }
+        // Fails with the error of the last rule checked
+        EligibilityRule::Or { rules } => {
+            let mut err = ContractError::NotEligible {};
+            for rule in rules {
+                match check_eligibility(deps, env, game_id, rule, depositor) {
+                    Ok(()) => return Ok(()),
+                    Err(rule_err) => err = rule_err,
+                }
+            }
+            Err(err)
+        }
+    }
+}
+
+// Depositor must own at least one ArchID that has not expired,
+// looking through every page of their tokens
+fn check_archid(
+    deps: Deps,
+    env: &Env,
+    contract_addr: &Addr,
+    depositor: &Addr,
+) -> Result<(), ContractError> {
+    let mut owns_archid = false;
+    let mut start_after: Option<String> = None;
+    loop {
         let query_msg: archid_token::QueryMsg<Extension> = Cw721QueryMsg::Tokens {
-            owner: info.sender.clone().into(),
-            start_after: None,
-            limit: None,
+            owner: depositor.clone().into(),
+            start_after,
+            limit: Some(ARCHID_PAGE_LIMIT),
         };
         let query_req = QueryRequest::Wasm(WasmQuery::Smart {
             contract_addr: contract_addr.to_string(),
-            msg: to_binary(&query_msg).unwrap(),
+            msg: to_binary(&query_msg)?,
         });
         let query_resp: TokensResponse = deps.querier.query(&query_req)?;
-        if query_resp.tokens.is_empty() {
-            return Err(ContractError::NoArchid {});
+
+        for token_id in query_resp.tokens.iter() {
+            owns_archid = true;
+            let query_msg: archid_token::QueryMsg<Extension> = Cw721QueryMsg::NftInfo {
+                token_id: token_id.clone(),
+            };
+            let query_req = QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: contract_addr.to_string(),
+                msg: to_binary(&query_msg)?,
+            });
+            let nft_info: NftInfoResponse<Extension> = deps.querier.query(&query_req)?;
+            // Domains without an expiry never expire
+            let expiry = nft_info.extension.and_then(|metadata| metadata.expiry);
+            match expiry {
+                Some(expiry) if expiry <= env.block.time.seconds() => {}
+                _ => return Ok(()),
+            }
         }
+
+        // Last page
+        if query_resp.tokens.len() < ARCHID_PAGE_LIMIT as usize {
+            break;
+        }
+        start_after = query_resp.tokens.last().cloned();
     }
 
-    // Sender must have sent correct funds
-    let required_payment = Coin {
-        denom: DENOM.to_string(),
-        amount: state.min_deposit,
-    };
-    check_sent_required_payment(&info.funds, Some(required_payment))?;
+    if owns_archid {
+        Err(ContractError::ExpiredArchid {})
+    } else {
+        Err(ContractError::NoArchid {})
+    }
+}
 
+#[allow(clippy::too_many_arguments)]
+fn deposit(
+    deps: DepsMut,
+    env: Env,
+    game_id: u64,
+    config: &Config,
+    mut state: RoundState,
+    depositor: Addr,
+    referrer: Option<Addr>,
+    quantity: u64,
+    cost: Uint128,
+) -> Result<Response, ContractError> {
     // Update state with deposit parameters
-    let new_expiration: u64 = state.expiration + state.extensions;
+    let mut new_expiration: u64 = state.expiration + config.extensions * quantity;
+    // Timer can't be pushed further than max_remaining from now
+    if let Some(max_remaining) = config.max_remaining {
+        new_expiration = new_expiration.min(env.block.time.seconds() + max_remaining);
+    }
     state.expiration = new_expiration;
     state.last_deposit = env.block.time.seconds();
-    state.last_depositor = info.sender.clone();
-    STATE.save(deps.storage, &state)?;
+    state.last_depositor = Some(depositor.clone());
+    // Names are best effort, a failing registry can't block deposits
+    state.last_depositor_name = resolve_archid(&deps.querier, config, &depositor).ok().flatten();
+    state.deposits += quantity;
+    state.deposited += cost;
+    CURRENT_ROUNDS.save(deps.storage, game_id, &state)?;
 
-    Ok(Response::new()
+    // Credit depositor's ledger
+    record_deposit(deps.storage, game_id, &depositor, state.round, quantity, cost)?;
+
+    // Accrue protocol fee
+    let fee = cost.multiply_ratio(config.fee_bps, BPS_DENOMINATOR);
+    if !fee.is_zero() {
+        let mut fees = FEES.may_load(deps.storage, game_id)?.unwrap_or_default();
+        fees.accrued += fee;
+        FEES.save(deps.storage, game_id, &fees)?;
+    }
+
+    // Referrers are paid from the deposit
+    let referral = match referrer {
+        Some(_) => cost.multiply_ratio(config.referral_bps, BPS_DENOMINATOR),
+        None => Uint128::zero(),
+    };
+
+    // Rest of the deposit goes to the pot
+    let pot = POTS.may_load(deps.storage, game_id)?.unwrap_or_default();
+    POTS.save(deps.storage, game_id, &(pot + cost - fee - referral))?;
+
+    let mut res = Response::new()
         .add_attribute("action", "execute_deposit")
+        .add_attribute("game_id", game_id.to_string())
         .add_attribute("round", state.round.to_string())
-        .add_attribute("depositor", info.sender))
+        .add_attribute("depositor", depositor)
+        .add_attribute("quantity", quantity.to_string())
+        .add_attribute("fee", fee);
+    if let Some(name) = state.last_depositor_name {
+        res = res.add_attribute("depositor_archid", name);
+    }
+
+    // Accrue referral reward
+    let referrer = match referrer {
+        Some(referrer) => referrer,
+        None => return Ok(res),
+    };
+    if !referral.is_zero() {
+        let earnings = REFERRAL_EARNINGS
+            .may_load(deps.storage, (game_id, &referrer))?
+            .unwrap_or_default();
+        REFERRAL_EARNINGS.save(deps.storage, (game_id, &referrer), &(earnings + referral))?;
+        let reserved = REFERRAL_RESERVES.may_load(deps.storage, game_id)?.unwrap_or_default();
+        REFERRAL_RESERVES.save(deps.storage, game_id, &(reserved + referral))?;
+    }
+
+    Ok(res
+        .add_attribute("referrer", referrer)
+        .add_attribute("referral", referral))
 }
 
 pub fn execute_claim(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    game_id: u64,
 ) -> Result<Response, ContractError> {
-    let state = STATE.load(deps.storage)?;
+    let (mut config, state) = load_game(deps.storage, game_id)?;
 
     // Game play must not be paused
     if state.is_paused() {
@@ -87,51 +692,97 @@ pub fn execute_claim(
     }
     
     // Caller must be winner
-    if info.sender != state.last_depositor {
+    if state.last_depositor.as_ref() != Some(&info.sender) {
         return Err(ContractError::Unauthorized {});
     }
 
-    // Query transferrable amount
-    let contract_funds = deps.querier.query_balance(env.contract.address, DENOM)?;
+    // Prize is forfeited once the round is stale
+    if state.is_stale(&env.block, &config) {
+        return Err(ContractError::ClaimExpired {});
+    }
 
-    // Transfer claim prizes
-    let bank_transfer_msg = BankMsg::Send {
-        to_address: info.sender.clone().into(),
-        amount: vec![contract_funds],
+    let prize_pool = POTS.may_load(deps.storage, game_id)?.unwrap_or_default();
+    let reserved = DIVIDEND_RESERVES.may_load(deps.storage, game_id)?.unwrap_or_default();
+
+    // Split prize pool between winner, depositors and next round
+    let prize = prize_pool.multiply_ratio(config.payout.winner_bps, BPS_DENOMINATOR);
+    let dividends = if state.deposited.is_zero() {
+        Uint128::zero()
+    } else {
+        prize_pool.multiply_ratio(config.payout.dividend_bps, BPS_DENOMINATOR)
+    };
+    let carry_over = prize_pool - prize - dividends;
+    DIVIDEND_RESERVES.save(deps.storage, game_id, &(reserved + dividends))?;
+    POTS.save(deps.storage, game_id, &carry_over)?;
+
+    // Record round results
+    let winner_name = resolve_archid(&deps.querier, &config, &info.sender).ok().flatten();
+    let record = RoundRecord {
+        round: state.round,
+        winner: Some(info.sender.clone()),
+        winner_name: winner_name.clone(),
+        prize,
+        start: state.round_start,
+        end: state.expiration,
+        deposits: state.deposits,
+        deposited: state.deposited,
+        dividends,
+        outcome: RoundOutcome::Claimed,
+        unlock_reward: Uint128::zero(),
     };
-    let bank_transfer: CosmosMsg = cosmwasm_std::CosmosMsg::Bank(bank_transfer_msg);
+    ROUNDS.save(deps.storage, (game_id, state.round), &record)?;
+
+    // Transfer claim prizes
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if !prize.is_zero() {
+        messages.push(config.asset.transfer_msg(&info.sender, prize)?);
+    }
 
     // Reset game
-    let new_expiration: u64 = env.block.time.seconds() + state.reset_length;
+    let new_expiration: u64 = env.block.time.seconds() + config.reset_length;
     let won_round = state.round.to_string();
     let round = state.round + 1;
-    let state_reset = State {
-        owner: state.owner,
+    let mut state_reset = RoundState {
         expiration: new_expiration,
-        min_deposit: state.min_deposit,
         last_deposit: env.block.time.seconds(),
-        last_depositor: info.sender.clone(),
-        extensions: state.extensions,
-        stale: state.stale,
-        reset_length: state.reset_length,
+        last_depositor: None,
         round,
         paused: None,
+        round_start: env.block.time.seconds(),
+        deposits: 0,
+        deposited: Uint128::zero(),
+        last_depositor_name: None,
+        carry_over,
     };
-    STATE.save(deps.storage, &state_reset)?;
+    // Queued configuration changes apply to the new round
+    apply_pending_config(deps.storage, deps.api, &env.block, game_id, &mut config, &mut state_reset)?;
+    CONFIGS.save(deps.storage, game_id, &config)?;
+    CURRENT_ROUNDS.save(deps.storage, game_id, &state_reset)?;
+    let sponsored = start_sponsored_round(deps.storage, game_id, round)?;
 
-    Ok(Response::new()
+    let mut res = Response::new()
         .add_attribute("action", "execute_claim")
+        .add_attribute("game_id", game_id.to_string())
         .add_attribute("winner", info.sender)
         .add_attribute("round", won_round)
-        .add_message(bank_transfer))
+        .add_attribute("prize", prize)
+        .add_attribute("dividends", dividends)
+        .add_attribute("carry_over", carry_over)
+        .add_attribute("sponsored", sponsored)
+        .add_messages(messages);
+    if let Some(name) = winner_name {
+        res = res.add_attribute("winner_archid", name);
+    }
+    Ok(res)
 }
 
 pub fn execute_unlock_stale(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    game_id: u64,
 ) -> Result<Response, ContractError> {
-    let state = STATE.load(deps.storage)?;
+    let (mut config, state) = load_game(deps.storage, game_id)?;
 
     // Game must not be paused for upgrades
     if state.is_paused() {
@@ -144,32 +795,182 @@ pub fn execute_unlock_stale(
     }
 
     // Game must be stale
-    if !state.is_stale(&env.block) {
+    if !state.is_stale(&env.block, &config) {
         return Err(ContractError::NotStale {});
     }
 
-    // Reset game, retaining the current prize pool
-    let new_expiration: u64 = env.block.time.seconds() + state.reset_length;
+    // Forfeited prize pool is split between the unlocker
+    // and the next round, rounds without deposits forfeit
+    // nothing and carry their whole pot over
+    let prize_pool = POTS.may_load(deps.storage, game_id)?.unwrap_or_default();
+    let unlock_reward = if state.deposits > 0 {
+        prize_pool
+            .multiply_ratio(config.unlock_reward_bps, BPS_DENOMINATOR)
+            .saturating_add(config.keeper_bounty)
+            .min(prize_pool)
+    } else {
+        Uint128::zero()
+    };
+    let carry_over = prize_pool - unlock_reward;
+    POTS.save(deps.storage, game_id, &carry_over)?;
+
+    // Record round results
+    let record = RoundRecord {
+        round: state.round,
+        winner: None,
+        winner_name: None,
+        prize: Uint128::zero(),
+        start: state.round_start,
+        end: state.expiration,
+        deposits: state.deposits,
+        deposited: state.deposited,
+        dividends: Uint128::zero(),
+        outcome: RoundOutcome::Stale,
+        unlock_reward,
+    };
+    ROUNDS.save(deps.storage, (game_id, state.round), &record)?;
+
+    // Transfer unlock reward
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if !unlock_reward.is_zero() {
+        messages.push(config.asset.transfer_msg(&info.sender, unlock_reward)?);
+    }
+
+    // Reset game
+    let new_expiration: u64 = env.block.time.seconds() + config.reset_length;
     let skipped_round = state.round.to_string();
     let round = state.round + 1;
-    let state_reset = State {
-        owner: state.owner,
+    let mut state_reset = RoundState {
         expiration: new_expiration,
-        min_deposit: state.min_deposit,
         last_deposit: env.block.time.seconds(),
-        last_depositor: info.sender.clone(),
-        extensions: state.extensions,
-        stale: state.stale,
-        reset_length: state.reset_length,
+        last_depositor: None,
         round,
         paused: None,
+        round_start: env.block.time.seconds(),
+        deposits: 0,
+        deposited: Uint128::zero(),
+        last_depositor_name: None,
+        carry_over,
     };
-
-    STATE.save(deps.storage, &state_reset)?;
+    // Queued configuration changes apply to the new round
+    apply_pending_config(deps.storage, deps.api, &env.block, game_id, &mut config, &mut state_reset)?;
+    CONFIGS.save(deps.storage, game_id, &config)?;
+    CURRENT_ROUNDS.save(deps.storage, game_id, &state_reset)?;
+    let sponsored = start_sponsored_round(deps.storage, game_id, round)?;
 
     Ok(Response::new()
         .add_attribute("action", "execute_unlock_stale")
-        .add_attribute("round", skipped_round))
+        .add_attribute("game_id", game_id.to_string())
+        .add_attribute("round", skipped_round)
+        .add_attribute("unlock_reward", unlock_reward)
+        .add_attribute("carry_over", carry_over)
+        .add_attribute("sponsored", sponsored)
+        .add_messages(messages))
+}
+
+// Withdraw dividends earned from settled rounds
+pub fn execute_withdraw_dividends(
+    deps: DepsMut,
+    info: MessageInfo,
+    game_id: u64,
+) -> Result<Response, ContractError> {
+    let (config, state) = load_game(deps.storage, game_id)?;
+
+    // Game play must not be paused
+    if state.is_paused() {
+        return Err(ContractError::Paused {});
+    }
+
+    // Sender must have dividends to withdraw
+    let mut player = PLAYERS
+        .may_load(deps.storage, (game_id, &info.sender))?
+        .ok_or(ContractError::NoDividends {})?;
+    let (amount, last_round) = pending_dividends(deps.storage, game_id, &info.sender, &player)?;
+    if amount.is_zero() {
+        return Err(ContractError::NoDividends {});
+    }
+
+    // Update player and reserve
+    player.dividends_round = last_round;
+    player.dividends_withdrawn += amount;
+    PLAYERS.save(deps.storage, (game_id, &info.sender), &player)?;
+    DIVIDEND_RESERVES.update(deps.storage, game_id, |reserved| -> StdResult<_> {
+        Ok(reserved.unwrap_or_default().checked_sub(amount)?)
+    })?;
+
+    // Transfer dividends
+    let transfer: CosmosMsg = config.asset.transfer_msg(&info.sender, amount)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_withdraw_dividends")
+        .add_attribute("player", info.sender)
+        .add_attribute("amount", amount)
+        .add_message(transfer))
+}
+
+// Register a persistent referrer (once per player)
+pub fn execute_set_referrer(
+    deps: DepsMut,
+    info: MessageInfo,
+    game_id: u64,
+    referrer: String,
+) -> Result<Response, ContractError> {
+    load_game(deps.storage, game_id)?;
+
+    // Referrer can't be changed
+    if REFERRERS.has(deps.storage, (game_id, &info.sender)) {
+        return Err(ContractError::ReferrerAlreadySet {});
+    }
+
+    let referrer = validate_referrer(deps.as_ref(), game_id, &info.sender, &referrer)?;
+    REFERRERS.save(deps.storage, (game_id, &info.sender), &referrer)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_set_referrer")
+        .add_attribute("player", info.sender)
+        .add_attribute("referrer", referrer))
+}
+
+// Withdraw rewards earned by referring other players
+pub fn execute_withdraw_referral_earnings(
+    deps: DepsMut,
+    info: MessageInfo,
+    game_id: u64,
+) -> Result<Response, ContractError> {
+    let (config, state) = load_game(deps.storage, game_id)?;
+
+    // Game play must not be paused
+    if state.is_paused() {
+        return Err(ContractError::Paused {});
+    }
+
+    // Sender must have earnings to withdraw
+    let amount = REFERRAL_EARNINGS
+        .may_load(deps.storage, (game_id, &info.sender))?
+        .unwrap_or_default();
+    if amount.is_zero() {
+        return Err(ContractError::NoReferralEarnings {});
+    }
+
+    // Update player and reserve
+    REFERRAL_EARNINGS.remove(deps.storage, (game_id, &info.sender));
+    let mut player = PLAYERS
+        .may_load(deps.storage, (game_id, &info.sender))?
+        .unwrap_or_default();
+    player.referrals_withdrawn += amount;
+    PLAYERS.save(deps.storage, (game_id, &info.sender), &player)?;
+    REFERRAL_RESERVES.update(deps.storage, game_id, |reserved| -> StdResult<_> {
+        Ok(reserved.unwrap_or_default().checked_sub(amount)?)
+    })?;
+
+    // Transfer earnings
+    let transfer: CosmosMsg = config.asset.transfer_msg(&info.sender, amount)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_withdraw_referral_earnings")
+        .add_attribute("referrer", info.sender)
+        .add_attribute("amount", amount)
+        .add_message(transfer))
 }
 
 // Pause game for upgrade (admin only)
@@ -177,25 +978,27 @@ pub fn execute_pause(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    game_id: u64,
 ) -> Result<Response, ContractError> {
-    let mut state = STATE.load(deps.storage)?;
+    let (_, mut state) = load_game(deps.storage, game_id)?;
 
     // Must not be paused already
     if state.is_paused() {
         return Err(ContractError::Paused {});
     }
 
-    // Only admin can pause
-    if info.sender != state.owner {
+    // Only pausers can pause
+    if !has_role(deps.storage, &info.sender, Role::Pauser)? {
         return Err(ContractError::Unauthorized {});
     }
 
     let paused_at: u64 = env.block.time.seconds();
     state.paused = Some(paused_at);
-    STATE.save(deps.storage, &state)?;
+    CURRENT_ROUNDS.save(deps.storage, game_id, &state)?;
 
     Ok(Response::new()
         .add_attribute("action", "execute_pause")
+        .add_attribute("game_id", game_id.to_string())
         .add_attribute("paused_at", paused_at.to_string()))
 }
 
@@ -204,16 +1007,17 @@ pub fn execute_unpause(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    game_id: u64,
 ) -> Result<Response, ContractError> {
-    let mut state = STATE.load(deps.storage)?;
+    let (_, mut state) = load_game(deps.storage, game_id)?;
 
     // Game must be paused
     if !state.is_paused() {
         return Err(ContractError::InvalidInput {});
     }
 
-    // Only Admin can unpause game
-    if info.sender != state.owner {
+    // Only pausers can unpause game
+    if !has_role(deps.storage, &info.sender, Role::Pauser)? {
         return Err(ContractError::Unauthorized {});
     }
 
@@ -223,71 +1027,542 @@ pub fn execute_unpause(
     let new_expiration: u64 = state.expiration + paused_duration;
     state.expiration = new_expiration;
     state.paused = None;
-    STATE.save(deps.storage, &state)?;
+    CURRENT_ROUNDS.save(deps.storage, game_id, &state)?;
 
     Ok(Response::new()
         .add_attribute("action", "execute_unpause")
+        .add_attribute("game_id", game_id.to_string())
         .add_attribute("unpaused_at", unpaused_at.to_string())
         .add_attribute("time_paused", paused_duration.to_string())
         .add_attribute("expiration", new_expiration.to_string()))
 }
 
+// Send accrued protocol fees to the fee recipient (admin only)
+pub fn execute_withdraw_fees(
+    deps: DepsMut,
+    info: MessageInfo,
+    game_id: u64,
+) -> Result<Response, ContractError> {
+    let (config, _) = load_game(deps.storage, game_id)?;
+
+    // Only fee managers can withdraw fees
+    if !has_role(deps.storage, &info.sender, Role::FeeManager)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Fees must have accrued
+    let mut fees = FEES.may_load(deps.storage, game_id)?.unwrap_or_default();
+    if fees.accrued.is_zero() {
+        return Err(ContractError::NoFees {});
+    }
+
+    let amount = fees.accrued;
+    fees.paid += amount;
+    fees.accrued = Uint128::zero();
+    FEES.save(deps.storage, game_id, &fees)?;
+
+    // Transfer fees (defaults to admin)
+    let recipient = config.fee_recipient
+        .or(OWNER.may_load(deps.storage)?)
+        .unwrap_or(info.sender);
+    let transfer: CosmosMsg = config.asset.transfer_msg(&recipient, amount)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_withdraw_fees")
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", amount)
+        .add_message(transfer))
+}
+
+// Send funds held above what the games owe, e.g. stray
+// transfers, to the recipient (admin only)
+pub fn execute_sweep(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: AssetInfo,
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    // Only fee managers can sweep
+    if !has_role(deps.storage, &info.sender, Role::FeeManager)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let asset = match asset {
+        AssetInfo::Native { denom } => AssetInfo::Native { denom },
+        AssetInfo::Cw20 { address } => AssetInfo::Cw20 { 
+            address: deps.api.addr_validate(address.as_str())?,
+        },
+    };
+    let recipient = recipient
+        .map(|recipient| deps.api.addr_validate(&recipient))
+        .transpose()?
+        .unwrap_or(info.sender);
+
+    // Balance must exceed what is owed to games
+    let balance = asset.query_balance(&deps.querier, &env.contract.address)?;
+    let accounted = accounted_balance(deps.storage, &asset)?;
+    let surplus = balance.saturating_sub(accounted);
+    if surplus.is_zero() {
+        return Err(ContractError::NoSurplus {});
+    }
+    let transfer: CosmosMsg = asset.transfer_msg(&recipient, surplus)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_sweep")
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", surplus)
+        .add_message(transfer))
+}
+
+// Offer ownership to a new admin (admin only)
+pub fn execute_propose_owner(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_owner: String,
+    expiry: Option<u64>,
+) -> Result<Response, ContractError> {
+    // Only admin can transfer ownership
+    if !is_owner(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(seconds) = expiry {
+        validate_duration("expiry", seconds)?;
+    }
+
+    // Replaces any previous proposal
+    let pending = PendingOwner {
+        owner: deps.api.addr_validate(&new_owner)?,
+        expiry: expiry.map(|seconds| env.block.time.seconds() + seconds),
+    };
+    PENDING_OWNER.save(deps.storage, &pending)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_propose_owner")
+        .add_attribute("pending_owner", pending.owner))
+}
+
+// Withdraw a pending ownership proposal (admin only)
+pub fn execute_cancel_ownership_proposal(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // Only admin can cancel
+    if !is_owner(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Proposal must exist
+    if PENDING_OWNER.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::NoPendingOwner {});
+    }
+    PENDING_OWNER.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_cancel_ownership_proposal"))
+}
+
+// Accept a pending ownership proposal (pending owner only)
+pub fn execute_accept_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // Proposal must exist, be addressed to 
+    // sender and not be expired
+    let pending = PENDING_OWNER
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingOwner {})?;
+    if info.sender != pending.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+    if pending.is_expired(&env.block) {
+        return Err(ContractError::OwnershipProposalExpired {});
+    }
+
+    OWNER.save(deps.storage, &pending.owner)?;
+    PENDING_OWNER.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_accept_ownership")
+        .add_attribute("owner", info.sender))
+}
+
+// Permanently give up admin rights (admin only)
+pub fn execute_renounce_ownership(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // Only admin can renounce
+    if !is_owner(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    OWNER.remove(deps.storage);
+    PENDING_OWNER.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_renounce_ownership"))
+}
+
+// Give an address an admin role (admin only)
+pub fn execute_grant_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    role: Role,
+) -> Result<Response, ContractError> {
+    // Only admin can grant roles
+    if !is_owner(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Ownership is transferred with ProposeOwner
+    if role == Role::Owner {
+        return Err(ContractError::OwnerRoleNotGrantable {});
+    }
+
+    let address = deps.api.addr_validate(&address)?;
+    let mut roles = ROLES.may_load(deps.storage, &address)?.unwrap_or_default();
+    if !roles.contains(&role) {
+        roles.push(role);
+        ROLES.save(deps.storage, &address, &roles)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_grant_role")
+        .add_attribute("address", address)
+        .add_attribute("role", format!("{:?}", role)))
+}
+
+// Remove an admin role from an address (admin only)
+pub fn execute_revoke_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    role: Role,
+) -> Result<Response, ContractError> {
+    // Only admin can revoke roles
+    if !is_owner(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Address must hold the role
+    let address = deps.api.addr_validate(&address)?;
+    let mut roles = ROLES.may_load(deps.storage, &address)?.unwrap_or_default();
+    if !roles.contains(&role) {
+        return Err(ContractError::InvalidInput {});
+    }
+    roles.retain(|granted| *granted != role);
+    if roles.is_empty() {
+        ROLES.remove(deps.storage, &address);
+    } else {
+        ROLES.save(deps.storage, &address, &roles)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_revoke_role")
+        .add_attribute("address", address)
+        .add_attribute("role", format!("{:?}", role)))
+}
+
+// Add and remove addresses from the allowlist (admin only)
+pub fn execute_update_allowlist(
+    deps: DepsMut,
+    info: MessageInfo,
+    game_id: u64,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    load_game(deps.storage, game_id)?;
+
+    // Only configurators can update the allowlist
+    if !has_role(deps.storage, &info.sender, Role::Configurator)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Must change at least 1 address
+    if add.is_empty() && remove.is_empty() {
+        return Err(ContractError::InvalidInput {});
+    }
+
+    for address in add.iter() {
+        let address = deps.api.addr_validate(address)?;
+        ALLOWLIST.save(deps.storage, (game_id, &address), &Empty {})?;
+    }
+    for address in remove.iter() {
+        let address = deps.api.addr_validate(address)?;
+        ALLOWLIST.remove(deps.storage, (game_id, &address));
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_update_allowlist")
+        .add_attribute("game_id", game_id.to_string())
+        .add_attribute("added", add.len().to_string())
+        .add_attribute("removed", remove.len().to_string()))
+}
+
 // Reconfigure game parameters (admin only)
 pub fn execute_configure(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
+    game_id: u64,
     msg: ConfigureMsg,
 ) -> Result<Response, ContractError> {
-    let mut state = STATE.load(deps.storage)?;
+    let (mut config, mut state) = load_game(deps.storage, game_id)?;
+
+    // Only configurators can reconfigure game
+    if !has_role(deps.storage, &info.sender, Role::Configurator)? {
+        return Err(ContractError::Unauthorized {});
+    }
 
-    // Only Admin can unpause game
-    if info.sender != state.owner {
+    // and only fee managers can change its fees
+    if (msg.fee_bps.is_some() || msg.fee_recipient.is_some())
+        && !has_role(deps.storage, &info.sender, Role::FeeManager)? {
         return Err(ContractError::Unauthorized {});
     }
 
     // Reconfiguration must change at least 1 value
-    if msg.owner.is_none() 
-        && msg.archid_registry.is_none()
+    if msg.archid_registry.is_none()
         && msg.expiration.is_none() 
         && msg.min_deposit.is_none()
         && msg.extensions.is_none()
         && msg.stale.is_none()
-        && msg.reset_length.is_none() {
+        && msg.reset_length.is_none()
+        && msg.payout.is_none()
+        && msg.fee_bps.is_none()
+        && msg.fee_recipient.is_none()
+        && msg.price_curve.is_none()
+        && msg.max_remaining.is_none()
+        && msg.max_round_duration.is_none()
+        && msg.funds_policy.is_none()
+        && msg.config_delay.is_none()
+        && msg.referral_bps.is_none()
+        && msg.unlock_reward_bps.is_none()
+        && msg.keeper_bounty.is_none()
+        && msg.eligibility.is_none() {
             return Err(ContractError::InvalidInput {});
         }
 
-    // Game settings
-    if let Some(new_owner) = msg.owner {
-        state.owner = new_owner;
+    // Queued change must be applied or cancelled first
+    if PENDING_CONFIGS.may_load(deps.storage, game_id)?.is_some() {
+        return Err(ContractError::ConfigPending {});
     }
-    if let Some(new_expiration) = msg.expiration {
-        state.expiration = new_expiration;
+
+    // Without a delay changes apply immediately
+    if config.config_delay == 0 {
+        update_config(deps.api, &env.block, &mut config, &mut state, msg)?;
+        CONFIGS.save(deps.storage, game_id, &config)?;
+        CURRENT_ROUNDS.save(deps.storage, game_id, &state)?;
+
+        return Ok(Response::new()
+            .add_attribute("action", "execute_configure")
+            .add_attribute("game_id", game_id.to_string()));
+    }
+
+    // Otherwise changes are validated and queued until the 
+    // next round starts or the delay has passed
+    update_config(deps.api, &env.block, &mut config.clone(), &mut state.clone(), msg.clone())?;
+    let pending = PendingConfig {
+        config: msg,
+        effective_at: env.block.time.seconds() + config.config_delay,
+        effective_round: state.round + 1,
+    };
+    PENDING_CONFIGS.save(deps.storage, game_id, &pending)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_configure")
+        .add_attribute("game_id", game_id.to_string())
+        .add_attribute("effective_at", pending.effective_at.to_string())
+        .add_attribute("effective_round", pending.effective_round.to_string()))
+}
+
+// Apply a queued configuration change once its delay has passed
+pub fn execute_apply_config(
+    deps: DepsMut,
+    env: Env,
+    game_id: u64,
+) -> Result<Response, ContractError> {
+    let (mut config, mut state) = load_game(deps.storage, game_id)?;
+
+    // Change must be queued and effective
+    let pending = PENDING_CONFIGS
+        .may_load(deps.storage, game_id)?
+        .ok_or(ContractError::NoPendingConfig {})?;
+    if !pending.is_effective(&env.block, state.round) {
+        return Err(ContractError::ConfigNotEffective {});
+    }
+
+    apply_pending_config(deps.storage, deps.api, &env.block, game_id, &mut config, &mut state)?;
+    CONFIGS.save(deps.storage, game_id, &config)?;
+    CURRENT_ROUNDS.save(deps.storage, game_id, &state)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_apply_config")
+        .add_attribute("game_id", game_id.to_string()))
+}
+
+// Drop a queued configuration change (configurators only)
+pub fn execute_cancel_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    game_id: u64,
+) -> Result<Response, ContractError> {
+    load_game(deps.storage, game_id)?;
+
+    // Only configurators can cancel changes
+    if !has_role(deps.storage, &info.sender, Role::Configurator)? {
+        return Err(ContractError::Unauthorized {});
     }
+
+    // Change must be queued
+    if PENDING_CONFIGS.may_load(deps.storage, game_id)?.is_none() {
+        return Err(ContractError::NoPendingConfig {});
+    }
+    PENDING_CONFIGS.remove(deps.storage, game_id);
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_cancel_config")
+        .add_attribute("game_id", game_id.to_string()))
+}
+
+// Apply the queued configuration change (if any)
+fn apply_pending_config(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    block: &BlockInfo,
+    game_id: u64,
+    config: &mut Config,
+    state: &mut RoundState,
+) -> Result<(), ContractError> {
+    if let Some(pending) = PENDING_CONFIGS.may_load(storage, game_id)? {
+        update_config(api, block, config, state, pending.config)?;
+        PENDING_CONFIGS.remove(storage, game_id);
+    }
+    Ok(())
+}
+
+// Validate and write configuration changes to the config
+// (and the current round's expiration)
+fn update_config(
+    api: &dyn Api,
+    block: &BlockInfo,
+    config: &mut Config,
+    state: &mut RoundState,
+    msg: ConfigureMsg,
+) -> Result<(), ContractError> {
+    // Game settings
+    let mut game_config = GameConfig::from_config(config);
     if let Some(new_min_deposit) = msg.min_deposit {
-        state.min_deposit = new_min_deposit;
+        game_config.min_deposit = new_min_deposit;
     }
     if let Some(new_extensions) = msg.extensions {
-        state.extensions = new_extensions;
+        game_config.extensions = new_extensions;
     }
     if let Some(new_stale) = msg.stale {
-        state.stale = new_stale;
+        game_config.stale = new_stale;
     }
     if let Some(new_reset_length) = msg.reset_length {
-        state.reset_length = new_reset_length;
+        game_config.reset_length = new_reset_length;
+    }
+    game_config.validate()?;
+    config.min_deposit = game_config.min_deposit;
+    config.extensions = game_config.extensions;
+    config.stale = game_config.stale;
+    config.reset_length = game_config.reset_length;
+
+    // Expiration is relative to when the change is applied
+    if let Some(new_expiration) = msg.expiration {
+        validate_duration("expiration", new_expiration)?;
+        state.expiration = block.time.seconds() + new_expiration;
+    }
+    if let Some(new_payout) = msg.payout {
+        if !new_payout.is_valid() {
+            return Err(ContractError::InvalidPayoutSplit {});
+        }
+        config.payout = new_payout;
+    }
+    // Fees and referral rewards are both taken from deposits
+    if let Some(new_fee_bps) = msg.fee_bps {
+        config.fee_bps = new_fee_bps;
+    }
+    if let Some(new_referral_bps) = msg.referral_bps {
+        config.referral_bps = new_referral_bps;
+    }
+    if config.fee_bps + config.referral_bps > BPS_DENOMINATOR {
+        return Err(ContractError::InvalidFee {});
+    }
+    if let Some(new_unlock_reward_bps) = msg.unlock_reward_bps {
+        validate_bps("unlock_reward_bps", new_unlock_reward_bps)?;
+        config.unlock_reward_bps = new_unlock_reward_bps;
+    }
+    if let Some(new_keeper_bounty) = msg.keeper_bounty {
+        config.keeper_bounty = new_keeper_bounty;
+    }
+    if let Some(new_fee_recipient) = msg.fee_recipient {
+        config.fee_recipient = Some(api.addr_validate(&new_fee_recipient)?);
+    }
+    // Steps are checked against the (possibly new) minimum deposit
+    if let Some(new_price_curve) = msg.price_curve {
+        config.price_curve = new_price_curve;
+    }
+    config.price_curve.validate(config.min_deposit)?;
+    // Setting a limit to 0 disables it
+    if let Some(new_max_remaining) = msg.max_remaining {
+        config.max_remaining = validate_limit("max_remaining", new_max_remaining)?;
+    }
+    if let Some(new_max_round_duration) = msg.max_round_duration {
+        config.max_round_duration = validate_limit("max_round_duration", new_max_round_duration)?;
+    }
+    if let Some(new_funds_policy) = msg.funds_policy {
+        config.funds_policy = new_funds_policy;
+    }
+    if let Some(new_config_delay) = msg.config_delay {
+        config.config_delay = validate_limit("config_delay", new_config_delay)?.unwrap_or_default();
     }
 
-    // ArchID settings
-    let registry = if msg.archid_registry.is_some() { msg.archid_registry } else { None };
-    let cw721 = if msg.archid_cw721.is_some() { msg.archid_cw721 } else { None };
-    if registry.is_some() && cw721.is_some() {
-        let archid = Archid {registry, cw721};
-        ARCHID.save(deps.storage, &archid)?;
+    if let Some(new_archid_registry) = msg.archid_registry {
+        config.archid_registry = Some(api.addr_validate(&new_archid_registry)?);
+    }
+    if let Some(new_eligibility) = msg.eligibility {
+        config.eligibility = new_eligibility.validate(api)?;
     }
 
-    STATE.save(deps.storage, &state)?;
+    Ok(())
+}
 
-    Ok(Response::new()
-        .add_attribute("action", "execute_configure"))
+pub fn record_deposit(
+    storage: &mut dyn Storage,
+    game_id: u64,
+    depositor: &Addr,
+    round: u64,
+    quantity: u64,
+    amount: Uint128,
+) -> StdResult<()> {
+    let mut round_stats = PLAYER_ROUNDS
+        .may_load(storage, (game_id, depositor, round))?
+        .unwrap_or_default();
+    let mut lifetime_stats = PLAYERS
+        .may_load(storage, (game_id, depositor))?
+        .unwrap_or_default();
+
+    // First deposit of the round
+    if round_stats.deposits == 0 {
+        lifetime_stats.rounds += 1;
+        ROUND_PLAYERS.save(storage, (game_id, round, depositor), &Empty {})?;
+    }
+
+    round_stats.deposits += quantity;
+    round_stats.total += amount;
+    lifetime_stats.deposits += quantity;
+    lifetime_stats.total += amount;
+
+    PLAYER_ROUNDS.save(storage, (game_id, depositor, round), &round_stats)?;
+    PLAYERS.save(storage, (game_id, depositor), &lifetime_stats)?;
+    Ok(())
 }
 
 pub fn check_sent_required_payment(
@@ -5,8 +5,8 @@ use cosmwasm_std::{
 use cw_multi_test::Executor;
 
 use crate::integration_tests::util::{
-    bank_query, create_archid, create_cw721, create_netwars, mint_native, mock_app,
-    query,
+    bank_query, create_archid, create_cw721, create_netwars, increment_block_time, mint_native, 
+    mock_app, query,
 };
 
 use archid_registry::{
@@ -14,10 +14,10 @@ use archid_registry::{
 };
 
 use crate::msg::{
-    ExecuteMsg, QueryMsg,
+    ExecuteMsg, QueryMsg, StatusResponse,
 };
-use crate::contract::DENOM;
-use crate::state::{State};
+use crate::integration_tests::util::DENOM;
+use crate::state::{RoundRecord, RoundState};
 
 #[test]
 fn test_enforce_archid() {
@@ -94,10 +94,10 @@ fn test_enforce_archid() {
     assert_eq!(netwars_balance.amount, Uint128::from(0_u128));
 
     // initial game state (no deposits)
-    let initial_game_state: State = query(
+    let initial_game_state: RoundState = query(
         &mut app,
         netwars_addr.clone(),
-        QueryMsg::Game{},
+        QueryMsg::CurrentRound{ game_id: 1 },
     ).unwrap();
 
     // exceuting deposit fails if sender 
@@ -106,7 +106,7 @@ fn test_enforce_archid() {
         .execute_contract(
             depositor.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Deposit{}, 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
             &[Coin {
                 denom: String::from(DENOM),
                 amount: Uint128::from(1000000000000000000_u128)
@@ -129,20 +129,28 @@ fn test_enforce_archid() {
     );
 
     // now depositor can make deposits
-    let _res = app.execute_contract(
+    let res = app.execute_contract(
         depositor.clone(), 
         netwars_addr.clone(), 
-        &ExecuteMsg::Deposit{}, 
+        &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
         &[Coin {
             denom: String::from(DENOM),
             amount: Uint128::from(1000000000000000000_u128)
         }]
-    );
+    ).unwrap();
+
+    // depositor's ArchID is resolved through the registry
+    let archid = res.events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == "depositor_archid")
+        .unwrap();
+    assert_eq!(archid.value, "first_archid.arch");
     
-    let game_query: State = query(
+    let game_query: RoundState = query(
         &mut app,
         netwars_addr.clone(),
-        QueryMsg::Game{},
+        QueryMsg::CurrentRound{ game_id: 1 },
     ).unwrap();
 
     // game expiration increased exactly as expected
@@ -152,4 +160,105 @@ fn test_enforce_archid() {
     // game prize increased exactly as expected
     let netwars_balance: Coin = bank_query(&mut app, &netwars_addr);
     assert_eq!(netwars_balance.amount, Uint128::from(1000000000000000000_u128));
-}
\ No newline at end of file
+
+    // leader is shown by ArchID
+    assert_eq!(game_query.last_depositor_name, Some(String::from("first_archid.arch")));
+    let status: StatusResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Status{ game_id: 1 },
+    ).unwrap();
+    assert_eq!(status.leader_name, Some(String::from("first_archid.arch")));
+
+    // depositor wins and claims round 1
+    increment_block_time(&mut app, game_query.expiration + 1, 7);
+    let res = app
+        .execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Claim{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+    let archid = res.events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == "winner_archid")
+        .unwrap();
+    assert_eq!(archid.value, "first_archid.arch");
+
+    // winner's ArchID is kept in the round history
+    let record: RoundRecord = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Round { game_id: 1, round: 1 },
+    ).unwrap();
+    assert_eq!(record.winner, Some(depositor));
+    assert_eq!(record.winner_name, Some(String::from("first_archid.arch")));
+}
+// ArchID names are best effort, games keep going when
+// the registry can't resolve them
+#[test]
+fn test_archid_registry_unavailable() {
+    let mut app = mock_app();
+
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // depositor owns ARCH
+    let depositor = Addr::unchecked("arch_owner");
+    mint_native(
+        &mut app,
+        depositor.to_string(),
+        Uint128::from(10000000000000000000_u128), // 10 ARCH as aarch
+    );
+
+    // registry address doesn't hold a contract
+    let netwars_addr: Addr = create_netwars(
+        &mut app, 
+        &netwars_admin, 
+        Some(Addr::unchecked("broken_registry")),
+        None,
+        120, // 2 minutes
+        Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+        30, // 30 seconds
+        604800, // ~1 week
+        604800, // ~1 week
+        &[],
+    );
+
+    // depositor can still deposit, without a name
+    let _res = app
+        .execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        )
+        .unwrap();
+    let status: StatusResponse = query(&mut app, netwars_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(status.leader, Some(depositor.clone()));
+    assert_eq!(status.leader_name, None);
+
+    // and claim the round
+    increment_block_time(&mut app, status.expiration + 1, 7);
+    let _res = app
+        .execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Claim{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+    let record: RoundRecord = query(
+        &mut app,
+        netwars_addr,
+        QueryMsg::Round { game_id: 1, round: 1 },
+    ).unwrap();
+    assert_eq!(record.winner, Some(depositor.clone()));
+    assert_eq!(record.winner_name, None);
+    let winner_balance: Coin = bank_query(&mut app, &depositor);
+    assert_eq!(winner_balance.amount, Uint128::from(10000000000000000000_u128));
+}
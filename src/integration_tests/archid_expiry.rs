@@ -0,0 +1,133 @@
+#![cfg(test)]
+use cosmwasm_std::{
+    Addr, Coin, Uint128,
+};
+use cw_multi_test::{App, Executor};
+
+use crate::integration_tests::util::{
+    create_archid, create_cw721, create_netwars, get_block_time, increment_block_time, mint_native,
+    mock_app,
+};
+
+use archid_registry::{
+    state::Config as RegistryConfig, msg::ExecuteMsg as ExecuteMsgArchid
+};
+
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+use crate::integration_tests::util::DENOM;
+
+fn register(app: &mut App, owner: &Addr, registry: &Addr, name: String) {
+    app.execute_contract(
+        owner.clone(),
+        registry.clone(),
+        &ExecuteMsgArchid::Register { name },
+        &[Coin {
+            denom: String::from(DENOM),
+            amount: Uint128::from(5000u128),
+        }],
+    )
+    .unwrap();
+}
+
+fn deposit(app: &mut App, depositor: &Addr, contract: &Addr) -> Result<(), String> {
+    app.execute_contract(
+        depositor.clone(),
+        contract.clone(),
+        &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None },
+        &[Coin {
+            denom: String::from(DENOM),
+            amount: Uint128::from(1000000000000000000_u128)
+        }]
+    )
+    .map(|_| ())
+    .map_err(|err| err.root_cause().to_string())
+}
+
+// Only unexpired ArchIDs make a player eligible, wherever
+// they are in the list of tokens the player owns
+#[test]
+fn test_archid_expiry() {
+    let mut app = mock_app();
+
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // depositor owns ARCH
+    let depositor = Addr::unchecked("arch_owner");
+    // newcomer owns ARCH but no ArchID
+    let newcomer = Addr::unchecked("newcomer");
+
+    // mint native to depositor and newcomer
+    mint_native(
+        &mut app,
+        depositor.to_string(),
+        Uint128::from(100000000000000000000_u128), // 100 ARCH as aarch
+    );
+    mint_native(
+        &mut app,
+        newcomer.to_string(),
+        Uint128::from(100000000000000000000_u128), // 100 ARCH as aarch
+    );
+
+    // netwars_admin deploys archid registry and token,
+    // domains expire after 1 day
+    let archid_addr = create_archid(
+        &mut app,
+        netwars_admin.clone(),
+        Addr::unchecked("empty"),
+        Uint128::from(5000u64),
+        86400,
+    );
+    let cw721_addr = create_cw721(&mut app, &archid_addr);
+    let update_msg = ExecuteMsgArchid::UpdateConfig {
+        config: RegistryConfig {
+            admin: netwars_admin.clone(),
+            wallet: netwars_admin.clone(),
+            cw721: cw721_addr.clone(),
+            base_cost: Uint128::from(5000u64),
+            base_expiration: 86400,
+        },
+    };
+    let _res = app
+        .execute_contract(netwars_admin.clone(), archid_addr.clone(), &update_msg, &[])
+        .unwrap();
+
+    // netwars_admin deploys netwars contract with archids enabled
+    let netwars_addr: Addr = create_netwars(
+        &mut app,
+        &netwars_admin,
+        Some(archid_addr.clone()),
+        Some(cw721_addr),
+        604800, // ~1 week
+        Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+        3600, // 1 hour
+        604800, // ~1 week
+        604800, // ~1 week
+        &[],
+    );
+
+    // players without an ArchID can't deposit
+    assert_eq!(
+        deposit(&mut app, &newcomer, &netwars_addr).unwrap_err(),
+        ContractError::NoArchid {}.to_string()
+    );
+
+    // depositor registers a full page of domains
+    for i in 0..30 {
+        register(&mut app, &depositor, &archid_addr, format!("expired_{:02}", i));
+    }
+    deposit(&mut app, &depositor, &netwars_addr).unwrap();
+
+    // all of depositor's domains expire
+    let current_time = get_block_time(&mut app);
+    increment_block_time(&mut app, current_time + 86400, 7);
+    assert_eq!(
+        deposit(&mut app, &depositor, &netwars_addr).unwrap_err(),
+        ContractError::ExpiredArchid {}.to_string()
+    );
+
+    // a fresh domain on the second page of tokens
+    // makes depositor eligible again
+    register(&mut app, &depositor, &archid_addr, String::from("fresh"));
+    deposit(&mut app, &depositor, &netwars_addr).unwrap();
+}
@@ -0,0 +1,141 @@
+#![cfg(test)]
+use cosmwasm_std::{
+    Addr, Coin, Uint128,
+};
+use cw_multi_test::Executor;
+
+use crate::integration_tests::util::{
+    bank_query, get_block_time, instantiate_msg, instantiate_netwars, mint_native, mock_app, query, DENOM,
+};
+
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, PlayerResponse, PriceResponse, QueryMsg,
+};
+use crate::state::{PlayerRoundStats, PriceCurve, RoundState};
+
+// Players can buy several units in one deposit, paying
+// the curve price of each unit and getting any
+// overpayment refunded
+#[test]
+fn test_bulk_deposit() {
+    let mut app = mock_app();
+    
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // depositor owns ARCH
+    let depositor = Addr::unchecked("arch_owner");
+
+    // mint arch to depositor
+    mint_native(
+        &mut app,
+        depositor.to_string(),
+        Uint128::from(10000000000000000000_u128), // 10 ARCH as aarch
+    );
+
+    // price increases by 0.5 ARCH per unit
+    let msg = InstantiateMsg {
+        price_curve: Some(PriceCurve::Linear { 
+            increment: Uint128::from(500000000000000000_u128),
+        }),
+        max_remaining: Some(240),
+        ..instantiate_msg()
+    };
+    let netwars_addr: Addr = instantiate_netwars(&mut app, &netwars_admin, &msg, &[]);
+    let initial_game_state: RoundState = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::CurrentRound{ game_id: 1 },
+    ).unwrap();
+
+    // quantity must be at least 1
+    assert!(
+        app.execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: Some(0), referrer: None }, 
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        ).is_err()
+    );
+
+    // 3 units cost 1 + 1.5 + 2 = 4.5 ARCH
+    assert!(
+        app.execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: Some(3), referrer: None }, 
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(4000000000000000000_u128) // Invalid amount (less than price)
+            }]
+        ).is_err()
+    );
+    let _res = app
+        .execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: Some(3), referrer: None }, 
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(5000000000000000000_u128)
+            }]
+        )
+        .unwrap();
+
+    // 0.5 ARCH overpayment was refunded
+    let netwars_balance: Coin = bank_query(&mut app, &netwars_addr);
+    assert_eq!(netwars_balance.amount, Uint128::from(4500000000000000000_u128));
+    let depositor_balance: Coin = bank_query(&mut app, &depositor);
+    assert_eq!(depositor_balance.amount, Uint128::from(5500000000000000000_u128));
+
+    // timer was extended once per unit
+    let game_query: RoundState = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::CurrentRound{ game_id: 1 },
+    ).unwrap();
+    assert_eq!(game_query.expiration, initial_game_state.expiration + 90);
+    assert_eq!(game_query.deposits, 3_u64);
+    assert_eq!(game_query.deposited, Uint128::from(4500000000000000000_u128));
+
+    // ledger was credited every unit
+    let res: PlayerResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Player { game_id: 1, address: depositor.to_string(), round: None },
+    ).unwrap();
+    assert_eq!(res.round_stats, PlayerRoundStats {
+        deposits: 3,
+        total: Uint128::from(4500000000000000000_u128),
+    });
+
+    // next unit costs 2.5 ARCH
+    let res: PriceResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::CurrentPrice{ game_id: 1 },
+    ).unwrap();
+    assert_eq!(res.price, Uint128::from(2500000000000000000_u128));
+
+    // bulk extensions are still capped by max_remaining
+    let _res = app
+        .execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: Some(2), referrer: None }, 
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(5500000000000000000_u128)
+            }]
+        )
+        .unwrap();
+    let current_time = get_block_time(&mut app);
+    let game_query: RoundState = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::CurrentRound{ game_id: 1 },
+    ).unwrap();
+    assert_eq!(game_query.expiration, current_time + 240);
+}
@@ -12,8 +12,8 @@ use crate::integration_tests::util::{
 use crate::msg::{
     ExecuteMsg, QueryMsg,
 };
-use crate::contract::DENOM;
-use crate::state::{State};
+use crate::integration_tests::util::DENOM;
+use crate::state::{RoundState};
 
 // When the game is won the winner can claim
 // all funds from the prize pool
@@ -74,7 +74,7 @@ fn test_claim() {
         .execute_contract(
             first_depositor.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Deposit{}, 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
             &[Coin {
                 denom: String::from(DENOM),
                 amount: Uint128::from(1000000000000000000_u128)
@@ -87,7 +87,7 @@ fn test_claim() {
         .execute_contract(
             second_depositor.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Deposit{}, 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
             &[Coin {
                 denom: String::from(DENOM),
                 amount: Uint128::from(1000000000000000000_u128)
@@ -106,7 +106,7 @@ fn test_claim() {
         app.execute_contract(
             first_depositor.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Deposit{}, 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
             &[Coin {
                 denom: String::from(DENOM),
                 amount: Uint128::from(1000000000000000000_u128)
@@ -119,7 +119,7 @@ fn test_claim() {
         .execute_contract(
             first_depositor.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Claim{}, 
+            &ExecuteMsg::Claim{ game_id: 1 }, 
             &[]
         )
         .is_err()
@@ -131,7 +131,7 @@ fn test_claim() {
         .execute_contract(
             second_depositor.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Claim{}, 
+            &ExecuteMsg::Claim{ game_id: 1 }, 
             &[]
         )
         .unwrap();
@@ -147,10 +147,10 @@ fn test_claim() {
 
     // game was correctly restarted, and
     // round was increased
-    let game_query: State = query(
+    let game_query: RoundState = query(
         &mut app,
         netwars_addr.clone(),
-        QueryMsg::Game{},
+        QueryMsg::CurrentRound{ game_id: 1 },
     ).unwrap();
     assert_eq!(game_query.round, 2_u64);
 
@@ -160,13 +160,14 @@ fn test_claim() {
         .execute_contract(
             first_depositor, 
             netwars_addr.clone(), 
-            &ExecuteMsg::Deposit{}, 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
             &[Coin {
                 denom: String::from(DENOM),
                 amount: Uint128::from(2000000000000000000_u128)
             }]
         )
         .unwrap();
+    // amounts above the price are refunded
     let netwars_balance: Coin = bank_query(&mut app, &netwars_addr);
-    assert_eq!(netwars_balance.amount, Uint128::from(2000000000000000000_u128));
+    assert_eq!(netwars_balance.amount, Uint128::from(1000000000000000000_u128));
 }
\ No newline at end of file
@@ -0,0 +1,147 @@
+#![cfg(test)]
+use cosmwasm_std::{
+    Addr, Coin, Uint128,
+};
+use cw_multi_test::Executor;
+
+use crate::integration_tests::util::{
+    bank_query, increment_block_time, instantiate_msg, instantiate_netwars, mint_native, mock_app, query, DENOM,
+};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigureMsg, ExecuteMsg, InstantiateMsg, QueryMsg, StatusResponse,
+};
+use crate::state::RoundRecord;
+
+// Winners can claim until the round is stale, after that the
+// prize is forfeited and split between whoever unlocks the
+// round and the next round's pot
+#[test]
+fn test_claim_deadline() {
+    let mut app = mock_app();
+
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // depositor owns ARCH
+    let depositor = Addr::unchecked("arch_owner");
+    // keeper unlocks stale rounds
+    let keeper = Addr::unchecked("keeper");
+
+    mint_native(
+        &mut app,
+        netwars_admin.to_string(),
+        Uint128::from(5000000000000000000_u128), // 5 ARCH as aarch
+    );
+    mint_native(
+        &mut app,
+        depositor.to_string(),
+        Uint128::from(10000000000000000000_u128), // 10 ARCH as aarch
+    );
+
+    // unlocker is rewarded 10% of a forfeited pot
+    let msg = InstantiateMsg {
+        stale: 600, // 10 minutes
+        reset_length: 120, // 2 minutes
+        unlock_reward_bps: Some(1000),
+        ..instantiate_msg()
+    };
+    let netwars_addr: Addr = instantiate_netwars(
+        &mut app,
+        &netwars_admin,
+        &msg,
+        &[Coin {
+            denom: String::from(DENOM),
+            amount: Uint128::from(5000000000000000000_u128)
+        }],
+    );
+
+    let deposit_msg = ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None };
+    let deposit_funds = [Coin {
+        denom: String::from(DENOM),
+        amount: Uint128::from(1000000000000000000_u128)
+    }];
+    let _res = app
+        .execute_contract(depositor.clone(), netwars_addr.clone(), &deposit_msg, &deposit_funds)
+        .unwrap();
+
+    // one second before the deadline the round can't
+    // be unlocked, and the winner can still claim
+    let status: StatusResponse = query(&mut app, netwars_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    increment_block_time(&mut app, status.stale_at - 1, 10);
+    let err = app
+        .execute_contract(keeper.clone(), netwars_addr.clone(), &ExecuteMsg::UnlockStale{ game_id: 1 }, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), ContractError::NotStale {}.to_string());
+    let _res = app
+        .execute_contract(depositor.clone(), netwars_addr.clone(), &ExecuteMsg::Claim{ game_id: 1 }, &[])
+        .unwrap();
+    let winner_balance: Coin = bank_query(&mut app, &depositor);
+    assert_eq!(winner_balance.amount, Uint128::from(15000000000000000000_u128));
+
+    // depositor leads round 2
+    let _res = app
+        .execute_contract(depositor.clone(), netwars_addr.clone(), &deposit_msg, &deposit_funds)
+        .unwrap();
+
+    // at the deadline the winner can no longer claim
+    let status: StatusResponse = query(&mut app, netwars_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(status.round, 2);
+    assert_eq!(status.pot, Uint128::from(1000000000000000000_u128));
+    increment_block_time(&mut app, status.stale_at, 10);
+    let err = app
+        .execute_contract(depositor.clone(), netwars_addr.clone(), &ExecuteMsg::Claim{ game_id: 1 }, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), ContractError::ClaimExpired {}.to_string());
+
+    // whoever unlocks the round takes the reward,
+    // the rest rolls over
+    let res = app
+        .execute_contract(keeper.clone(), netwars_addr.clone(), &ExecuteMsg::UnlockStale{ game_id: 1 }, &[])
+        .unwrap();
+    let unlock_reward = res.events
+        .iter()
+        .flat_map(|e| e.attributes.iter())
+        .find(|a| a.key == "unlock_reward")
+        .unwrap();
+    assert_eq!(unlock_reward.value, "100000000000000000");
+    let keeper_balance: Coin = bank_query(&mut app, &keeper);
+    assert_eq!(keeper_balance.amount, Uint128::from(100000000000000000_u128));
+    let status: StatusResponse = query(&mut app, netwars_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(status.round, 3);
+    assert_eq!(status.pot, Uint128::from(900000000000000000_u128));
+    assert_eq!(status.carry_over, Uint128::from(900000000000000000_u128));
+    let record: RoundRecord = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Round { game_id: 1, round: 2 },
+    ).unwrap();
+    assert_eq!(record.winner, None);
+    assert_eq!(record.unlock_reward, Uint128::from(100000000000000000_u128));
+
+    // losing the race leaves the winner nothing to claim
+    let err = app
+        .execute_contract(depositor.clone(), netwars_addr.clone(), &ExecuteMsg::Claim{ game_id: 1 }, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), ContractError::Gameover {}.to_string());
+    let netwars_balance: Coin = bank_query(&mut app, &netwars_addr);
+    assert_eq!(netwars_balance.amount, Uint128::from(900000000000000000_u128));
+
+    // reward can't exceed the whole pot
+    let configure_msg = ConfigureMsg {
+        unlock_reward_bps: Some(10001),
+        ..Default::default()
+    };
+    let err = app
+        .execute_contract(
+            netwars_admin.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Configure { game_id: 1, msg: configure_msg },
+            &[]
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        ContractError::ValueTooLarge { field: "unlock_reward_bps".to_string(), max: 10000 }.to_string()
+    );
+}
@@ -0,0 +1,168 @@
+#![cfg(test)]
+use cosmwasm_std::{
+    Addr, Coin, to_binary, Uint128,
+};
+use cw20::{Cw20Coin, Cw20ExecuteMsg};
+use cw_multi_test::Executor;
+
+use crate::integration_tests::util::{
+    create_cw20, cw20_balance_query, increment_block_time, get_block_time, instantiate_msg, instantiate_netwars, 
+    mint_native, mock_app, query, DENOM,
+};
+
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, QueryMsg, ReceiveMsg,
+};
+use crate::state::{AssetInfo, RoundState};
+
+// Games can be played with a cw20 token; deposits are 
+// made through the token's Send hook and prizes are
+// paid in the same token
+#[test]
+fn test_cw20_game() {
+    let mut app = mock_app();
+    
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // first_depositor owns NET tokens
+    let first_depositor = Addr::unchecked("net_owner");
+    // second_depositor owns NET tokens
+    let second_depositor = Addr::unchecked("second_net_owner");
+
+    // netwars_admin deploys the game token, and
+    // another unrelated token
+    let token = create_cw20(
+        &mut app,
+        &netwars_admin,
+        vec![
+            Cw20Coin {
+                address: first_depositor.to_string(),
+                amount: Uint128::from(10000000000000000000_u128), // 10 NET
+            },
+            Cw20Coin {
+                address: second_depositor.to_string(),
+                amount: Uint128::from(10000000000000000000_u128), // 10 NET
+            },
+        ],
+    );
+    let other_token = create_cw20(
+        &mut app,
+        &netwars_admin,
+        vec![
+            Cw20Coin {
+                address: first_depositor.to_string(),
+                amount: Uint128::from(10000000000000000000_u128), // 10 OTHER
+            },
+        ],
+    );
+    // first_depositor also owns ARCH
+    mint_native(
+        &mut app,
+        first_depositor.to_string(),
+        Uint128::from(10000000000000000000_u128), // 10 ARCH as aarch
+    );
+
+    // netwars_admin creates a game played with NET
+    let msg = InstantiateMsg {
+        asset: AssetInfo::Cw20 { address: token.clone() },
+        min_deposit: Uint128::from(1000000000000000000_u128), // 1 NET
+        ..instantiate_msg()
+    };
+    let netwars_addr: Addr = instantiate_netwars(&mut app, &netwars_admin, &msg, &[]);
+    let initial_game_state: RoundState = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::CurrentRound{ game_id: 1 },
+    ).unwrap();
+
+    // native deposits are rejected
+    assert!(
+        app.execute_contract(
+            first_depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        ).is_err()
+    );
+
+    // deposits of other tokens are rejected
+    let deposit_msg = Cw20ExecuteMsg::Send {
+        contract: netwars_addr.to_string(),
+        amount: Uint128::from(1000000000000000000_u128),
+        msg: to_binary(&ReceiveMsg::Deposit{ game_id: 1, quantity: None, referrer: None }).unwrap(),
+    };
+    assert!(
+        app.execute_contract(
+            first_depositor.clone(), 
+            other_token.clone(), 
+            &deposit_msg, 
+            &[]
+        ).is_err()
+    );
+
+    // deposits below min_deposit are rejected
+    assert!(
+        app.execute_contract(
+            first_depositor.clone(), 
+            token.clone(), 
+            &Cw20ExecuteMsg::Send {
+                contract: netwars_addr.to_string(),
+                amount: Uint128::from(1000_u128),
+                msg: to_binary(&ReceiveMsg::Deposit{ game_id: 1, quantity: None, referrer: None }).unwrap(),
+            }, 
+            &[]
+        ).is_err()
+    );
+
+    // first_depositor and second_depositor deposit NET
+    let _res = app
+        .execute_contract(
+            first_depositor.clone(), 
+            token.clone(), 
+            &deposit_msg, 
+            &[]
+        )
+        .unwrap();
+    let _res = app
+        .execute_contract(
+            second_depositor.clone(), 
+            token.clone(), 
+            &deposit_msg, 
+            &[]
+        )
+        .unwrap();
+
+    // the timer was extended and the depositor 
+    // recorded, as with native deposits
+    let game_query: RoundState = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::CurrentRound{ game_id: 1 },
+    ).unwrap();
+    assert_eq!(game_query.expiration, initial_game_state.expiration + 60);
+    assert_eq!(game_query.last_depositor, Some(second_depositor.clone()));
+    assert_eq!(
+        cw20_balance_query(&app, &token, &netwars_addr),
+        Uint128::from(2000000000000000000_u128)
+    );
+
+    // second_depositor wins and is paid in NET
+    let current_time = get_block_time(&mut app);
+    increment_block_time(&mut app, current_time + 1000, 7);
+    let _res = app
+        .execute_contract(
+            second_depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Claim{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+    assert_eq!(
+        cw20_balance_query(&app, &token, &second_depositor),
+        Uint128::from(11000000000000000000_u128)
+    );
+    assert_eq!(cw20_balance_query(&app, &token, &netwars_addr), Uint128::zero());
+}
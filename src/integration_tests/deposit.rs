@@ -11,8 +11,8 @@ use crate::integration_tests::util::{
 use crate::msg::{
     ExecuteMsg, QueryMsg,
 };
-use crate::contract::DENOM;
-use crate::state::{State};
+use crate::integration_tests::util::DENOM;
+use crate::state::{RoundState};
 
 // Valid deposits must increase the timer,  
 // invalid deposits must return an error
@@ -62,7 +62,7 @@ fn test_deposit() {
         app.execute_contract(
             depositor.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Deposit{}, 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
             &[Coin {
                 denom: String::from(DENOM),
                 amount: Uint128::from(1000_u128) // Invalid amount (less than min_deposit)
@@ -71,26 +71,26 @@ fn test_deposit() {
     );
 
     // depositing a valid amount must increase the game timer
-    let initial_game_state: State = query(
+    let initial_game_state: RoundState = query(
         &mut app,
         netwars_addr.clone(),
-        QueryMsg::Game{},
+        QueryMsg::CurrentRound{ game_id: 1 },
     ).unwrap();
     let _res = app
         .execute_contract(
             depositor.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Deposit{}, 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
             &[Coin {
                 denom: String::from(DENOM),
                 amount: Uint128::from(1000000000000000000_u128)
             }]
         )
         .unwrap();
-    let game_query: State = query(
+    let game_query: RoundState = query(
         &mut app,
         netwars_addr.clone(),
-        QueryMsg::Game{},
+        QueryMsg::CurrentRound{ game_id: 1 },
     ).unwrap();
     let expected_expiration: u64 = initial_game_state.expiration + extension_length;
     assert_eq!(game_query.expiration, expected_expiration);
@@ -0,0 +1,231 @@
+#![cfg(test)]
+use cosmwasm_std::{
+    Addr, Coin, Uint128,
+};
+use cw_multi_test::Executor;
+
+use crate::integration_tests::util::{
+    bank_query, increment_block_time, get_block_time, instantiate_msg, instantiate_netwars, mint_native, 
+    mock_app, query,
+};
+
+use crate::msg::{
+    DividendsResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+use crate::integration_tests::util::DENOM;
+use crate::state::{PayoutSplit, RoundRecord};
+
+// When a round is claimed the prize pool is split between 
+// the winner, the round's depositors (pro-rata) and the 
+// next round. Depositors withdraw their own dividends
+#[test]
+fn test_dividends() {
+    let mut app = mock_app();
+    
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // first_depositor owns ARCH
+    let first_depositor = Addr::unchecked("arch_owner");
+    // second_depositor owns ARCH
+    let second_depositor = Addr::unchecked("second_arch_owner");
+
+    // mint arch to first_depositor and second_depositor
+    mint_native(
+        &mut app,
+        first_depositor.to_string(),
+        Uint128::from(10000000000000000000_u128), // 10 ARCH as aarch
+    );
+    mint_native(
+        &mut app,
+        second_depositor.to_string(),
+        Uint128::from(10000000000000000000_u128), // 10 ARCH as aarch
+    );
+
+    // payout splits must add up to 100%
+    let mut msg = InstantiateMsg {
+        payout: Some(PayoutSplit {
+            winner_bps: 5000,
+            dividend_bps: 3000,
+            carry_over_bps: 3000,
+        }),
+        ..instantiate_msg()
+    };
+    let netwars_id = app.store_code(crate::integration_tests::util::contract_netwars());
+    assert!(
+        app.instantiate_contract(netwars_id, netwars_admin.clone(), &msg, &[], "Netwars", None)
+            .is_err()
+    );
+
+    // 50% to winner, 30% to depositors, 20% to next round
+    msg.payout = Some(PayoutSplit {
+        winner_bps: 5000,
+        dividend_bps: 3000,
+        carry_over_bps: 2000,
+    });
+    let netwars_addr: Addr = instantiate_netwars(&mut app, &netwars_admin, &msg, &[]);
+
+    // first_depositor deposits 1 ARCH
+    let _res = app
+        .execute_contract(
+            first_depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        )
+        .unwrap();
+
+    // second_depositor buys 3 units for 3 ARCH
+    let _res = app
+        .execute_contract(
+            second_depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: Some(3), referrer: None }, 
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(3000000000000000000_u128)
+            }]
+        )
+        .unwrap();
+
+    // no dividends before the round is settled
+    assert!(
+        app.execute_contract(
+            first_depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::WithdrawDividends{ game_id: 1 }, 
+            &[]
+        ).is_err()
+    );
+
+    // second_depositor wins round 1 (4 ARCH prize pool)
+    let current_time = get_block_time(&mut app);
+    increment_block_time(&mut app, current_time + 1000, 7);
+    let _res = app
+        .execute_contract(
+            second_depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Claim{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+
+    // winner received 50% (2 ARCH)
+    let winner_balance: Coin = bank_query(&mut app, &second_depositor);
+    assert_eq!(winner_balance.amount, Uint128::from(9000000000000000000_u128));
+
+    // 30% (1.2 ARCH) is shared between depositors
+    let record: RoundRecord = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Round { game_id: 1, round: 1 },
+    ).unwrap();
+    assert_eq!(record.prize, Uint128::from(2000000000000000000_u128));
+    assert_eq!(record.dividends, Uint128::from(1200000000000000000_u128));
+
+    // first_depositor deposited 25% of the round
+    let res: DividendsResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Dividends { game_id: 1, address: first_depositor.to_string() },
+    ).unwrap();
+    assert_eq!(res.pending, Uint128::from(300000000000000000_u128));
+    assert_eq!(res.withdrawn, Uint128::zero());
+
+    // second_depositor deposited 75% of the round
+    let res: DividendsResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Dividends { game_id: 1, address: second_depositor.to_string() },
+    ).unwrap();
+    assert_eq!(res.pending, Uint128::from(900000000000000000_u128));
+
+    // first_depositor withdraws dividends
+    let _res = app
+        .execute_contract(
+            first_depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::WithdrawDividends{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+    let depositor_balance: Coin = bank_query(&mut app, &first_depositor);
+    assert_eq!(depositor_balance.amount, Uint128::from(9300000000000000000_u128));
+
+    // dividends can't be withdrawn twice
+    assert!(
+        app.execute_contract(
+            first_depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::WithdrawDividends{ game_id: 1 }, 
+            &[]
+        ).is_err()
+    );
+    let res: DividendsResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Dividends { game_id: 1, address: first_depositor.to_string() },
+    ).unwrap();
+    assert_eq!(res.pending, Uint128::zero());
+    assert_eq!(res.withdrawn, Uint128::from(300000000000000000_u128));
+
+    // first_depositor wins round 2 with a 1 ARCH deposit
+    let _res = app
+        .execute_contract(
+            first_depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        )
+        .unwrap();
+    let current_time = get_block_time(&mut app);
+    increment_block_time(&mut app, current_time + 700000, 7);
+    let _res = app
+        .execute_contract(
+            first_depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Claim{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+
+    // second_depositor's unwithdrawn dividends were not part 
+    // of the prize pool (0.8 ARCH carry over + 1 ARCH deposit)
+    let record: RoundRecord = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Round { game_id: 1, round: 2 },
+    ).unwrap();
+    assert_eq!(record.prize, Uint128::from(900000000000000000_u128));
+    assert_eq!(record.dividends, Uint128::from(540000000000000000_u128));
+
+    // second_depositor withdraws dividends from round 1
+    let _res = app
+        .execute_contract(
+            second_depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::WithdrawDividends{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+    let depositor_balance: Coin = bank_query(&mut app, &second_depositor);
+    assert_eq!(depositor_balance.amount, Uint128::from(9900000000000000000_u128));
+
+    // first_depositor was the only depositor of round 2
+    let res: DividendsResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Dividends { game_id: 1, address: first_depositor.to_string() },
+    ).unwrap();
+    assert_eq!(res.pending, Uint128::from(540000000000000000_u128));
+
+    // contract holds first_depositor's dividends and the carry over
+    // (0.54 ARCH dividends + 0.36 ARCH carry over)
+    let netwars_balance: Coin = bank_query(&mut app, &netwars_addr);
+    assert_eq!(netwars_balance.amount, Uint128::from(900000000000000000_u128));
+}
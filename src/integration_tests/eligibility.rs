@@ -0,0 +1,221 @@
+#![cfg(test)]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{
+    Addr, Binary, Coin, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult,
+    to_binary, Uint128,
+};
+use cw4::{Cw4QueryMsg, MemberResponse};
+use cw20::Cw20Coin;
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_storage_plus::Map;
+
+use crate::integration_tests::util::{
+    create_cw20, instantiate_msg, instantiate_netwars, mint_native, mock_app, query, DENOM,
+};
+
+use crate::error::ContractError;
+use crate::msg::{
+    AllowlistResponse, ConfigureMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+use crate::state::EligibilityRule;
+
+// Stands in for a cw4 group, where every member has a weight of 1
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+struct GroupInstantiateMsg {
+    members: Vec<String>,
+}
+
+const MEMBERS: Map<&str, u64> = Map::new("members");
+
+fn group_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: GroupInstantiateMsg,
+) -> StdResult<Response> {
+    for member in msg.members.iter() {
+        MEMBERS.save(deps.storage, member, &1)?;
+    }
+    Ok(Response::default())
+}
+
+fn group_execute(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+    Err(StdError::generic_err("not supported"))
+}
+
+fn group_query(deps: Deps, _env: Env, msg: Cw4QueryMsg) -> StdResult<Binary> {
+    match msg {
+        Cw4QueryMsg::Member { addr, .. } => to_binary(&MemberResponse {
+            weight: MEMBERS.may_load(deps.storage, &addr)?,
+        }),
+        _ => Err(StdError::generic_err("not supported")),
+    }
+}
+
+fn contract_group() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(group_execute, group_instantiate, group_query);
+    Box::new(contract)
+}
+
+fn deposit(app: &mut App, depositor: &Addr, contract: &Addr) -> Result<(), String> {
+    app.execute_contract(
+        depositor.clone(),
+        contract.clone(),
+        &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None },
+        &[Coin {
+            denom: String::from(DENOM),
+            amount: Uint128::from(1000000000000000000_u128)
+        }]
+    )
+    .map(|_| ())
+    .map_err(|err| err.root_cause().to_string())
+}
+
+// Deposits can be gated by combining eligibility rules, here
+// players must be allowlisted, or be group members who hold
+// enough of the community token
+#[test]
+fn test_eligibility() {
+    let mut app = mock_app();
+
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // allowlisted player
+    let guest = Addr::unchecked("guest");
+    // group member holding enough tokens
+    let member = Addr::unchecked("member");
+    // group member without enough tokens
+    let poor_member = Addr::unchecked("poor_member");
+    // holds enough tokens but isn't a member
+    let outsider = Addr::unchecked("outsider");
+
+    for player in [&guest, &member, &poor_member, &outsider] {
+        mint_native(
+            &mut app,
+            player.to_string(),
+            Uint128::from(10000000000000000000_u128), // 10 ARCH as aarch
+        );
+    }
+
+    // netwars_admin deploys the group and community token
+    let group_id = app.store_code(contract_group());
+    let group = app
+        .instantiate_contract(
+            group_id,
+            netwars_admin.clone(),
+            &GroupInstantiateMsg {
+                members: vec![member.to_string(), poor_member.to_string()],
+            },
+            &[],
+            "group",
+            None,
+        )
+        .unwrap();
+    let token = create_cw20(
+        &mut app,
+        &netwars_admin,
+        vec![
+            Cw20Coin {
+                address: member.to_string(),
+                amount: Uint128::from(100_u128),
+            },
+            Cw20Coin {
+                address: poor_member.to_string(),
+                amount: Uint128::from(99_u128),
+            },
+            Cw20Coin {
+                address: outsider.to_string(),
+                amount: Uint128::from(100_u128),
+            },
+        ],
+    );
+
+    let eligibility = EligibilityRule::Or {
+        rules: vec![
+            EligibilityRule::Allowlist {},
+            EligibilityRule::And {
+                rules: vec![
+                    EligibilityRule::Cw4Member { group },
+                    EligibilityRule::Cw20MinBalance {
+                        token,
+                        amount: Uint128::from(100_u128),
+                    },
+                ],
+            },
+        ],
+    };
+    let msg = InstantiateMsg {
+        expiration: 604800, // ~1 week
+        extensions: 3600, // 1 hour
+        eligibility: Some(eligibility),
+        ..instantiate_msg()
+    };
+    let netwars_addr: Addr = instantiate_netwars(&mut app, &netwars_admin, &msg, &[]);
+
+    // group members need enough tokens, and holders
+    // need to be group members
+    deposit(&mut app, &member, &netwars_addr).unwrap();
+    assert_eq!(
+        deposit(&mut app, &poor_member, &netwars_addr).unwrap_err(),
+        ContractError::NotEligible {}.to_string()
+    );
+    assert_eq!(
+        deposit(&mut app, &outsider, &netwars_addr).unwrap_err(),
+        ContractError::NotEligible {}.to_string()
+    );
+
+    // only configurators can update the allowlist
+    let update_msg = ExecuteMsg::UpdateAllowlist {
+        game_id: 1,
+        add: vec![guest.to_string(), outsider.to_string()],
+        remove: vec![],
+    };
+    let err = app
+        .execute_contract(guest.clone(), netwars_addr.clone(), &update_msg, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), ContractError::Unauthorized {}.to_string());
+    let _res = app
+        .execute_contract(netwars_admin.clone(), netwars_addr.clone(), &update_msg, &[])
+        .unwrap();
+    let res: AllowlistResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Allowlist { game_id: 1, start_after: None, limit: None },
+    ).unwrap();
+    assert_eq!(res.addresses, vec![guest.clone(), outsider.clone()]);
+
+    // allowlisted players can deposit
+    deposit(&mut app, &guest, &netwars_addr).unwrap();
+    deposit(&mut app, &outsider, &netwars_addr).unwrap();
+
+    // removed players can't
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::UpdateAllowlist { game_id: 1, add: vec![], remove: vec![outsider.to_string()] },
+            &[]
+        )
+        .unwrap();
+    assert_eq!(
+        deposit(&mut app, &outsider, &netwars_addr).unwrap_err(),
+        ContractError::NotEligible {}.to_string()
+    );
+
+    // lifting the rules lets anyone deposit
+    let configure_msg = ConfigureMsg {
+        eligibility: Some(EligibilityRule::None {}),
+        ..Default::default()
+    };
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Configure { game_id: 1, msg: configure_msg },
+            &[]
+        )
+        .unwrap();
+    deposit(&mut app, &poor_member, &netwars_addr).unwrap();
+}
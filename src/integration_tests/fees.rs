@@ -0,0 +1,160 @@
+#![cfg(test)]
+use cosmwasm_std::{
+    Addr, Coin, Uint128,
+};
+use cw_multi_test::Executor;
+
+use crate::integration_tests::util::{
+    bank_query, increment_block_time, get_block_time, instantiate_msg, instantiate_netwars, mint_native, 
+    mock_app, query,
+};
+
+use crate::msg::{
+    ConfigureMsg, ExecuteMsg, FeesResponse, InstantiateMsg, QueryMsg,
+};
+use crate::integration_tests::util::DENOM;
+
+// Deposits accrue a protocol fee that is not part of 
+// the prize pool, and that admin can send to the fee 
+// recipient
+#[test]
+fn test_fees() {
+    let mut app = mock_app();
+    
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // treasury receives protocol fees
+    let treasury = Addr::unchecked("treasury");
+    // depositor owns ARCH
+    let depositor = Addr::unchecked("arch_owner");
+
+    // mint arch to depositor
+    mint_native(
+        &mut app,
+        depositor.to_string(),
+        Uint128::from(10000000000000000000_u128), // 10 ARCH as aarch
+    );
+
+    // fee is 5% of each deposit
+    let msg = InstantiateMsg {
+        fee_bps: Some(500),
+        fee_recipient: Some(treasury.to_string()),
+        ..instantiate_msg()
+    };
+    let netwars_addr: Addr = instantiate_netwars(&mut app, &netwars_admin, &msg, &[]);
+
+    // depositor makes two deposits
+    for _ in 0..2 {
+        let _res = app
+            .execute_contract(
+                depositor.clone(), 
+                netwars_addr.clone(), 
+                &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
+                &[Coin {
+                    denom: String::from(DENOM),
+                    amount: Uint128::from(1000000000000000000_u128)
+                }]
+            )
+            .unwrap();
+    }
+
+    // 0.1 ARCH in fees has accrued
+    let res: FeesResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Fees{ game_id: 1 },
+    ).unwrap();
+    assert_eq!(res, FeesResponse {
+        fee_bps: 500,
+        fee_recipient: Some(treasury.clone()),
+        accrued: Uint128::from(100000000000000000_u128),
+        paid: Uint128::zero(),
+    });
+
+    // depositor wins and claims the prize pool, 
+    // minus the accrued fees
+    let current_time = get_block_time(&mut app);
+    increment_block_time(&mut app, current_time + 1000, 7);
+    let _res = app
+        .execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Claim{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+    let depositor_balance: Coin = bank_query(&mut app, &depositor);
+    assert_eq!(depositor_balance.amount, Uint128::from(9900000000000000000_u128));
+    let netwars_balance: Coin = bank_query(&mut app, &netwars_addr);
+    assert_eq!(netwars_balance.amount, Uint128::from(100000000000000000_u128));
+
+    // random can't withdraw fees
+    assert!(
+        app.execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::WithdrawFees{ game_id: 1 }, 
+            &[]
+        ).is_err()
+    );
+
+    // admin sends fees to the treasury
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::WithdrawFees{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+    let treasury_balance: Coin = bank_query(&mut app, &treasury);
+    assert_eq!(treasury_balance.amount, Uint128::from(100000000000000000_u128));
+    let res: FeesResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Fees{ game_id: 1 },
+    ).unwrap();
+    assert_eq!(res.accrued, Uint128::zero());
+    assert_eq!(res.paid, Uint128::from(100000000000000000_u128));
+
+    // fees can't be withdrawn twice
+    assert!(
+        app.execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::WithdrawFees{ game_id: 1 }, 
+            &[]
+        ).is_err()
+    );
+
+    // fee can't exceed 100%
+    let mut config_msg = ConfigureMsg {
+        fee_bps: Some(10001),
+        ..Default::default()
+    };
+    assert!(
+        app.execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Configure { game_id: 1, msg: config_msg.clone() }, 
+            &[]
+        ).is_err()
+    );
+
+    // admin can change the fee
+    config_msg.fee_bps = Some(1000);
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Configure { game_id: 1, msg: config_msg }, 
+            &[]
+        )
+        .unwrap();
+    let res: FeesResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Fees{ game_id: 1 },
+    ).unwrap();
+    assert_eq!(res.fee_bps, 1000);
+}
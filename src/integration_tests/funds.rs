@@ -0,0 +1,145 @@
+#![cfg(test)]
+use cosmwasm_std::{
+    Addr, Coin, Uint128,
+};
+use cw_multi_test::{BankSudo, Executor, SudoMsg};
+
+use crate::integration_tests::util::{
+    bank_query, instantiate_msg, instantiate_netwars, mint_native, mock_app, DENOM,
+};
+
+use crate::msg::{
+    ConfigureMsg, ExecuteMsg, InstantiateMsg,
+};
+use crate::state::FundsPolicy;
+
+static OTHER_DENOM: &str = "uatom";
+
+// Overpayment and coins of other denoms are either
+// refunded or rejected depending on the funds policy
+#[test]
+fn test_funds_policy() {
+    let mut app = mock_app();
+    
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // depositor owns ARCH and ATOM
+    let depositor = Addr::unchecked("arch_owner");
+
+    // mint arch and atom to depositor
+    mint_native(
+        &mut app,
+        depositor.to_string(),
+        Uint128::from(10000000000000000000_u128), // 10 ARCH as aarch
+    );
+    app.sudo(SudoMsg::Bank(
+        BankSudo::Mint {
+            to_address: depositor.to_string(),
+            amount: vec![Coin {
+                denom: OTHER_DENOM.to_string(),
+                amount: Uint128::from(1000000_u128), // 1 ATOM as uatom
+            }],
+        },
+    ))
+    .unwrap();
+
+    let msg = InstantiateMsg {
+        funds_policy: Some(FundsPolicy::Reject),
+        ..instantiate_msg()
+    };
+    let netwars_addr: Addr = instantiate_netwars(&mut app, &netwars_admin, &msg, &[]);
+
+    let overpayment = vec![Coin {
+        denom: String::from(DENOM),
+        amount: Uint128::from(1500000000000000000_u128)
+    }];
+    let mixed_funds = vec![
+        Coin {
+            denom: String::from(DENOM),
+            amount: Uint128::from(1000000000000000000_u128)
+        },
+        Coin {
+            denom: OTHER_DENOM.to_string(),
+            amount: Uint128::from(1000000_u128)
+        },
+    ];
+
+    // overpayment is rejected
+    assert!(
+        app.execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
+            &overpayment
+        ).is_err()
+    );
+
+    // foreign denoms are rejected
+    assert!(
+        app.execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
+            &mixed_funds
+        ).is_err()
+    );
+
+    // exact payment is accepted
+    let _res = app
+        .execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        )
+        .unwrap();
+
+    // admin switches to refunds
+    let config_msg = ConfigureMsg {
+        funds_policy: Some(FundsPolicy::Refund),
+        ..Default::default()
+    };
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Configure { game_id: 1, msg: config_msg }, 
+            &[]
+        )
+        .unwrap();
+
+    // overpayment is refunded
+    let _res = app
+        .execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
+            &overpayment
+        )
+        .unwrap();
+    let netwars_balance: Coin = bank_query(&mut app, &netwars_addr);
+    assert_eq!(netwars_balance.amount, Uint128::from(2000000000000000000_u128));
+
+    // foreign denoms are refunded
+    let _res = app
+        .execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
+            &mixed_funds
+        )
+        .unwrap();
+    let netwars_balance: Coin = bank_query(&mut app, &netwars_addr);
+    assert_eq!(netwars_balance.amount, Uint128::from(3000000000000000000_u128));
+    let netwars_other_balance = app.wrap().query_balance(&netwars_addr, OTHER_DENOM).unwrap();
+    assert_eq!(netwars_other_balance.amount, Uint128::zero());
+    let depositor_other_balance = app.wrap().query_balance(&depositor, OTHER_DENOM).unwrap();
+    assert_eq!(depositor_other_balance.amount, Uint128::from(1000000_u128));
+
+    // depositor only paid for 3 deposits
+    let depositor_balance: Coin = bank_query(&mut app, &depositor);
+    assert_eq!(depositor_balance.amount, Uint128::from(7000000000000000000_u128));
+}
@@ -0,0 +1,239 @@
+#![cfg(test)]
+use cosmwasm_std::{
+    Addr, Coin, Uint128,
+};
+use cw_multi_test::Executor;
+
+use crate::integration_tests::util::{
+    bank_query, increment_block_time, get_block_time, instantiate_msg, instantiate_netwars,
+    mint_native, mock_app, query, DENOM,
+};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ExecuteMsg, GamesResponse, InstantiateMsg, QueryMsg, StatusResponse,
+};
+use crate::state::RoundState;
+
+fn game_msg(expiration: u64) -> InstantiateMsg {
+    InstantiateMsg {
+        expiration,
+        ..instantiate_msg()
+    }
+}
+
+// One contract can host several games, each with
+// its own settings, rounds and pot
+#[test]
+fn test_games() {
+    let mut app = mock_app();
+
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // plays the first game
+    let first_player = Addr::unchecked("first_player");
+    // plays the second game
+    let second_player = Addr::unchecked("second_player");
+
+    mint_native(
+        &mut app,
+        netwars_admin.to_string(),
+        Uint128::from(10000000000000000000_u128), // 10 ARCH as aarch
+    );
+    for player in [&first_player, &second_player] {
+        mint_native(
+            &mut app,
+            player.to_string(),
+            Uint128::from(10000000000000000000_u128), // 10 ARCH as aarch
+        );
+    }
+
+    // contract is instantiated with game 1, seeded with 5 ARCH
+    let netwars_addr: Addr = instantiate_netwars(
+        &mut app,
+        &netwars_admin,
+        &game_msg(120),
+        &[Coin {
+            denom: String::from(DENOM),
+            amount: Uint128::from(5000000000000000000_u128)
+        }],
+    );
+
+    // only configurators can create games
+    let err = app
+        .execute_contract(
+            first_player.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::CreateGame { config: game_msg(600) },
+            &[]
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), ContractError::Unauthorized {}.to_string());
+
+    // game 2 runs longer, seeded with 2 ARCH
+    let res = app
+        .execute_contract(
+            netwars_admin.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::CreateGame { config: game_msg(600) },
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(2000000000000000000_u128)
+            }]
+        )
+        .unwrap();
+    let game_id = res.events
+        .iter()
+        .flat_map(|e| e.attributes.iter())
+        .find(|a| a.key == "game_id")
+        .unwrap();
+    assert_eq!(game_id.value, "2");
+
+    let games: GamesResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Games { start_after: None, limit: None },
+    ).unwrap();
+    assert_eq!(games.games.len(), 2);
+    assert_eq!(games.games[0].game_id, 1);
+    assert_eq!(games.games[0].config.min_deposit, Uint128::from(1000000000000000000_u128));
+    assert_eq!(games.games[1].game_id, 2);
+    let games: GamesResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Games { start_after: Some(1), limit: None },
+    ).unwrap();
+    assert_eq!(games.games.len(), 1);
+    assert_eq!(games.games[0].game_id, 2);
+
+    // unknown games are rejected
+    let err = app
+        .execute_contract(
+            first_player.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Deposit{ game_id: 3, quantity: None, referrer: None },
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), ContractError::GameNotFound { game_id: 3 }.to_string());
+
+    // each player deposits into their own game
+    for (player, game_id) in [(&first_player, 1), (&second_player, 2)] {
+        let _res = app
+            .execute_contract(
+                player.clone(),
+                netwars_addr.clone(),
+                &ExecuteMsg::Deposit{ game_id, quantity: None, referrer: None },
+                &[Coin {
+                    denom: String::from(DENOM),
+                    amount: Uint128::from(1000000000000000000_u128)
+                }]
+            )
+            .unwrap();
+    }
+
+    // pots are kept apart
+    let status: StatusResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Status{ game_id: 1 },
+    ).unwrap();
+    assert_eq!(status.pot, Uint128::from(6000000000000000000_u128));
+    assert_eq!(status.leader, Some(first_player.clone()));
+    let status: StatusResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Status{ game_id: 2 },
+    ).unwrap();
+    assert_eq!(status.pot, Uint128::from(3000000000000000000_u128));
+    assert_eq!(status.leader, Some(second_player.clone()));
+
+    // game 1 expires while game 2 is still running
+    let current_time = get_block_time(&mut app);
+    increment_block_time(&mut app, current_time + 300, 7);
+
+    // winning game 1 doesn't pay out game 2's pot
+    assert!(app
+        .execute_contract(
+            second_player.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Claim{ game_id: 2 },
+            &[]
+        )
+        .is_err()
+    );
+    let _res = app
+        .execute_contract(
+            first_player.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Claim{ game_id: 1 },
+            &[]
+        )
+        .unwrap();
+    let winner_balance: Coin = bank_query(&mut app, &first_player);
+    assert_eq!(winner_balance.amount, Uint128::from(15000000000000000000_u128));
+    let netwars_balance: Coin = bank_query(&mut app, &netwars_addr);
+    assert_eq!(netwars_balance.amount, Uint128::from(3000000000000000000_u128));
+    let status: StatusResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Status{ game_id: 2 },
+    ).unwrap();
+    assert_eq!(status.pot, Uint128::from(3000000000000000000_u128));
+
+    // game 1 restarted, game 2 is untouched
+    let round: RoundState = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::CurrentRound{ game_id: 1 },
+    ).unwrap();
+    assert_eq!(round.round, 2);
+    let round: RoundState = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::CurrentRound{ game_id: 2 },
+    ).unwrap();
+    assert_eq!(round.round, 1);
+    assert_eq!(round.last_depositor, Some(second_player.clone()));
+
+    // pausing one game leaves the other playable
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Pause{ game_id: 1 },
+            &[]
+        )
+        .unwrap();
+    let _res = app
+        .execute_contract(
+            first_player.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Deposit{ game_id: 2, quantity: None, referrer: None },
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        )
+        .unwrap();
+
+    // game 2's winner takes its whole pot, both games
+    // being played with the same denom
+    let current_time = get_block_time(&mut app);
+    increment_block_time(&mut app, current_time + 600, 7);
+    let _res = app
+        .execute_contract(
+            first_player.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Claim{ game_id: 2 },
+            &[]
+        )
+        .unwrap();
+    let winner_balance: Coin = bank_query(&mut app, &first_player);
+    assert_eq!(winner_balance.amount, Uint128::from(18000000000000000000_u128));
+    let netwars_balance: Coin = bank_query(&mut app, &netwars_addr);
+    assert_eq!(netwars_balance.amount, Uint128::zero());
+}
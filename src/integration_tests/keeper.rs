@@ -0,0 +1,213 @@
+#![cfg(test)]
+use cosmwasm_std::{
+    Addr, Coin, Uint128,
+};
+use cw_multi_test::{App, Executor};
+
+use crate::integration_tests::util::{
+    bank_query, increment_block_time, instantiate_msg, instantiate_netwars, mint_native, mock_app, query, DENOM,
+};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigureMsg, ExecuteMsg, InstantiateMsg, QueryMsg, StatusResponse,
+};
+use crate::state::RoundState;
+
+fn claim(app: &mut App, caller: &Addr, contract: &Addr) -> Result<(), String> {
+    app.execute_contract(caller.clone(), contract.clone(), &ExecuteMsg::Claim{ game_id: 1 }, &[])
+        .map(|_| ())
+        .map_err(|err| err.root_cause().to_string())
+}
+
+fn unlock(app: &mut App, caller: &Addr, contract: &Addr) -> Result<(), String> {
+    app.execute_contract(caller.clone(), contract.clone(), &ExecuteMsg::UnlockStale{ game_id: 1 }, &[])
+        .map(|_| ())
+        .map_err(|err| err.root_cause().to_string())
+}
+
+// Whoever unlocks a stale round someone played earns the keeper
+// bounty, but rounds only have a leader once someone deposits,
+// so neither keepers nor past winners can claim a round they
+// didn't play
+#[test]
+fn test_keeper() {
+    let mut app = mock_app();
+
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // depositor owns ARCH
+    let depositor = Addr::unchecked("arch_owner");
+    // keeper unlocks stale rounds, never depositing
+    let keeper = Addr::unchecked("keeper");
+
+    mint_native(
+        &mut app,
+        netwars_admin.to_string(),
+        Uint128::from(6000000000000000000_u128), // 6 ARCH as aarch
+    );
+    mint_native(
+        &mut app,
+        depositor.to_string(),
+        Uint128::from(10000000000000000000_u128), // 10 ARCH as aarch
+    );
+
+    // keepers earn 0.5 ARCH per unlock
+    let msg = InstantiateMsg {
+        stale: 600, // 10 minutes
+        reset_length: 120, // 2 minutes
+        keeper_bounty: Some(Uint128::from(500000000000000000_u128)),
+        ..instantiate_msg()
+    };
+    let netwars_addr: Addr = instantiate_netwars(
+        &mut app,
+        &netwars_admin,
+        &msg,
+        &[Coin {
+            denom: String::from(DENOM),
+            amount: Uint128::from(5000000000000000000_u128)
+        }],
+    );
+
+    // creating the game doesn't make the admin its leader
+    let round: RoundState = query(&mut app, netwars_addr.clone(), QueryMsg::CurrentRound{ game_id: 1 }).unwrap();
+    assert_eq!(round.last_depositor, None);
+    let status: StatusResponse = query(&mut app, netwars_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(status.stale_at, status.expiration);
+    assert_eq!(
+        unlock(&mut app, &keeper, &netwars_addr).unwrap_err(),
+        ContractError::Gameover {}.to_string()
+    );
+    increment_block_time(&mut app, status.expiration, 10);
+    assert_eq!(
+        claim(&mut app, &netwars_admin, &netwars_addr).unwrap_err(),
+        ContractError::Unauthorized {}.to_string()
+    );
+
+    // a round nobody played rolls over as soon as it ends,
+    // paying no bounty, its pot carries over in full
+    unlock(&mut app, &keeper, &netwars_addr).unwrap();
+    let keeper_balance: Coin = bank_query(&mut app, &keeper);
+    assert_eq!(keeper_balance.amount, Uint128::zero());
+
+    // unlocking doesn't make the keeper the next round's leader
+    let round: RoundState = query(&mut app, netwars_addr.clone(), QueryMsg::CurrentRound{ game_id: 1 }).unwrap();
+    assert_eq!(round.round, 2);
+    assert_eq!(round.last_depositor, None);
+    let status: StatusResponse = query(&mut app, netwars_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(status.leader, None);
+    assert_eq!(status.pot, Uint128::from(5000000000000000000_u128));
+
+    // depositor leads the round but never claims it
+    let _res = app
+        .execute_contract(
+            depositor.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None },
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        )
+        .unwrap();
+    let status: StatusResponse = query(&mut app, netwars_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    increment_block_time(&mut app, status.expiration, 10);
+    assert_eq!(
+        claim(&mut app, &keeper, &netwars_addr).unwrap_err(),
+        ContractError::Unauthorized {}.to_string()
+    );
+
+    // keeper unlocks the forfeited round for the bounty
+    increment_block_time(&mut app, status.stale_at, 10);
+    unlock(&mut app, &keeper, &netwars_addr).unwrap();
+    let keeper_balance: Coin = bank_query(&mut app, &keeper);
+    assert_eq!(keeper_balance.amount, Uint128::from(500000000000000000_u128));
+    let status: StatusResponse = query(&mut app, netwars_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(status.round, 3);
+    assert_eq!(status.leader, None);
+    assert_eq!(status.pot, Uint128::from(5500000000000000000_u128));
+
+    // depositing players still win
+    let _res = app
+        .execute_contract(
+            depositor.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None },
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        )
+        .unwrap();
+    let status: StatusResponse = query(&mut app, netwars_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(status.leader, Some(depositor.clone()));
+    increment_block_time(&mut app, status.expiration, 10);
+    assert_eq!(
+        claim(&mut app, &keeper, &netwars_addr).unwrap_err(),
+        ContractError::Unauthorized {}.to_string()
+    );
+    claim(&mut app, &depositor, &netwars_addr).unwrap();
+    let winner_balance: Coin = bank_query(&mut app, &depositor);
+    assert_eq!(winner_balance.amount, Uint128::from(14500000000000000000_u128));
+
+    // winners don't lead the next round either
+    let status: StatusResponse = query(&mut app, netwars_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(status.round, 4);
+    assert_eq!(status.leader, None);
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Fund{ game_id: 1, round: None },
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(200000000000000000_u128)
+            }]
+        )
+        .unwrap();
+    increment_block_time(&mut app, status.expiration, 10);
+    assert_eq!(
+        claim(&mut app, &depositor, &netwars_addr).unwrap_err(),
+        ContractError::Unauthorized {}.to_string()
+    );
+
+    // and the sponsored pot can't be taken by unlocking
+    unlock(&mut app, &keeper, &netwars_addr).unwrap();
+    let keeper_balance: Coin = bank_query(&mut app, &keeper);
+    assert_eq!(keeper_balance.amount, Uint128::from(500000000000000000_u128));
+    let status: StatusResponse = query(&mut app, netwars_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(status.round, 5);
+    assert_eq!(status.pot, Uint128::from(200000000000000000_u128));
+
+    // bounty is capped by the pot
+    let configure_msg = ConfigureMsg {
+        keeper_bounty: Some(Uint128::from(2000000000000000000_u128)),
+        ..Default::default()
+    };
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Configure { game_id: 1, msg: configure_msg },
+            &[]
+        )
+        .unwrap();
+    let _res = app
+        .execute_contract(
+            depositor.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None },
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        )
+        .unwrap();
+    let status: StatusResponse = query(&mut app, netwars_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    increment_block_time(&mut app, status.stale_at, 10);
+    unlock(&mut app, &keeper, &netwars_addr).unwrap();
+    let keeper_balance: Coin = bank_query(&mut app, &keeper);
+    assert_eq!(keeper_balance.amount, Uint128::from(1700000000000000000_u128));
+    let netwars_balance: Coin = bank_query(&mut app, &netwars_addr);
+    assert_eq!(netwars_balance.amount, Uint128::zero());
+}
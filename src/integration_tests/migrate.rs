@@ -0,0 +1,503 @@
+#![cfg(test)]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{
+    Addr, Coin, DepsMut, Empty, Env, MessageInfo, Response, Uint128,
+};
+use cw2::set_contract_version;
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use cw_storage_plus::Item;
+
+use crate::integration_tests::util::{
+    bank_query, contract_netwars, get_block_time, increment_block_time, mint_native, mock_app,
+    query, DENOM,
+};
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, MigrateMsg, OwnershipResponse, QueryMsg, StatusResponse};
+use crate::state::{Archid, AssetInfo, Config, EligibilityRule, LEGACY_ARCHID, RoundState};
+
+// State item as stored by 0.1.4 and earlier
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+struct BaselineState {
+    owner: Addr,
+    expiration: u64,
+    min_deposit: Uint128,
+    last_deposit: u64,
+    last_depositor: Addr,
+    extensions: u64,
+    stale: u64,
+    reset_length: u64,
+    round: u64,
+    paused: Option<u64>,
+}
+
+const BASELINE_STATE: Item<BaselineState> = Item::new("state");
+
+// Leader of the round in progress when the game is upgraded
+const LEGACY_LEADER: &str = "legacy_leader";
+
+// Deploys a game stored in the layout of 0.1.4 and earlier
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+struct LegacyInstantiateMsg {
+    contract: String,
+    version: String,
+    paused: bool,
+    // Only stored by 0.1.4
+    archid: Option<Archid>,
+}
+
+fn legacy_instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: LegacyInstantiateMsg,
+) -> Result<Response, ContractError> {
+    let now = env.block.time.seconds();
+    let state = BaselineState {
+        owner: info.sender,
+        expiration: now + 120,
+        min_deposit: Uint128::from(1000000000000000000_u128),
+        last_deposit: now,
+        last_depositor: Addr::unchecked(LEGACY_LEADER),
+        extensions: 30,
+        stale: 604800,
+        reset_length: 604800,
+        round: 4,
+        paused: msg.paused.then(|| now),
+    };
+    BASELINE_STATE.save(deps.storage, &state)?;
+    if let Some(archid) = msg.archid {
+        LEGACY_ARCHID.save(deps.storage, &archid)?;
+    }
+    set_contract_version(deps.storage, msg.contract, msg.version)?;
+    Ok(Response::default())
+}
+
+fn contract_legacy() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        legacy_instantiate,
+        crate::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn create_legacy(app: &mut App, owner: &Addr, msg: LegacyInstantiateMsg, funds: &[Coin]) -> Addr {
+    let legacy_id = app.store_code(contract_legacy());
+    app.instantiate_contract(
+        legacy_id,
+        owner.clone(),
+        &msg,
+        funds,
+        "Netwars",
+        Some(owner.to_string()),
+    )
+    .unwrap()
+}
+
+fn legacy_msg(version: &str) -> LegacyInstantiateMsg {
+    LegacyInstantiateMsg {
+        contract: "network-wars".to_string(),
+        version: version.to_string(),
+        paused: false,
+        archid: None,
+    }
+}
+
+fn migrations(res: &AppResponse) -> String {
+    res.events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == "migrations")
+        .unwrap()
+        .value
+        .clone()
+}
+
+// Deploys a game from raw storage items, as written by
+// an earlier version
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+struct RawInstantiateMsg {
+    version: String,
+    items: Vec<(String, String)>,
+}
+
+fn raw_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: RawInstantiateMsg,
+) -> Result<Response, ContractError> {
+    for (key, value) in msg.items {
+        deps.storage.set(key.as_bytes(), value.as_bytes());
+    }
+    set_contract_version(deps.storage, "network-wars", msg.version)?;
+    Ok(Response::default())
+}
+
+fn create_raw(app: &mut App, owner: &Addr, msg: RawInstantiateMsg, funds: &[Coin]) -> Addr {
+    let raw_id = app.store_code(Box::new(ContractWrapper::new(
+        crate::contract::execute,
+        raw_instantiate,
+        crate::contract::query,
+    )));
+    app.instantiate_contract(
+        raw_id,
+        owner.clone(),
+        &msg,
+        funds,
+        "Netwars",
+        Some(owner.to_string()),
+    )
+    .unwrap()
+}
+
+fn migrate_msg() -> MigrateMsg {
+    MigrateMsg {
+        archid_registry: None,
+        archid_cw721: None,
+        legacy_denom: Some(DENOM.to_string()),
+        require_paused: None,
+    }
+}
+
+// Upgrades run every migration step newer than the stored
+// version, in order, and refuse anything else
+#[test]
+fn test_migrate() {
+    let mut app = mock_app();
+
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // depositor owns ARCH
+    let depositor = Addr::unchecked("arch_owner");
+    let legacy_leader = Addr::unchecked(LEGACY_LEADER);
+    for address in [&netwars_admin, &depositor] {
+        mint_native(
+            &mut app,
+            address.to_string(),
+            Uint128::from(10000000000000000000_u128), // 10 ARCH as aarch
+        );
+    }
+    let netwars_id = app.store_code(contract_netwars());
+    // every legacy game holds a 2 ARCH pot
+    let pot = [Coin {
+        denom: String::from(DENOM),
+        amount: Uint128::from(2000000000000000000_u128)
+    }];
+
+    // 0.1.3 games take their ArchID contracts from the migrate msg
+    let game_addr = create_legacy(&mut app, &netwars_admin, legacy_msg("0.1.3"), &pot);
+    let msg = MigrateMsg {
+        archid_registry: Some("archid_registry".to_string()),
+        archid_cw721: Some("archid_cw721".to_string()),
+        ..migrate_msg()
+    };
+    let res = app
+        .migrate_contract(netwars_admin.clone(), game_addr.clone(), &msg, netwars_id)
+        .unwrap();
+    assert_eq!(migrations(&res), "0.3.1");
+    let ownership: OwnershipResponse = query(&mut app, game_addr.clone(), QueryMsg::Ownership{}).unwrap();
+    assert_eq!(ownership.owner, Some(netwars_admin.clone()));
+    let config: Config = query(&mut app, game_addr.clone(), QueryMsg::Config{ game_id: 1 }).unwrap();
+    assert_eq!(config.asset, AssetInfo::Native { denom: DENOM.to_string() });
+    assert_eq!(config.archid_registry, Some(Addr::unchecked("archid_registry")));
+    assert_eq!(config.eligibility, EligibilityRule::ArchId { cw721: Addr::unchecked("archid_cw721") });
+    let status: StatusResponse = query(&mut app, game_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(status.round, 4_u64);
+    assert_eq!(status.leader, Some(legacy_leader.clone()));
+    assert_eq!(status.pot, Uint128::from(2000000000000000000_u128));
+
+    // 0.1.4 games keep their stored ArchID contracts
+    let game_addr = create_legacy(
+        &mut app,
+        &netwars_admin,
+        LegacyInstantiateMsg {
+            archid: Some(Archid { registry: None, cw721: Some(Addr::unchecked("archid_cw721")) }),
+            ..legacy_msg("0.1.4")
+        },
+        &pot,
+    );
+    let res = app
+        .migrate_contract(netwars_admin.clone(), game_addr.clone(), &migrate_msg(), netwars_id)
+        .unwrap();
+    assert_eq!(migrations(&res), "0.3.1");
+    let ownership: OwnershipResponse = query(&mut app, game_addr.clone(), QueryMsg::Ownership{}).unwrap();
+    assert_eq!(ownership.owner, Some(netwars_admin.clone()));
+    let config: Config = query(&mut app, game_addr.clone(), QueryMsg::Config{ game_id: 1 }).unwrap();
+    assert_eq!(config.archid_registry, None);
+    assert_eq!(config.eligibility, EligibilityRule::ArchId { cw721: Addr::unchecked("archid_cw721") });
+    let status: StatusResponse = query(&mut app, game_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(status.round, 4_u64);
+    assert_eq!(status.leader, Some(legacy_leader.clone()));
+    assert_eq!(status.pot, Uint128::from(2000000000000000000_u128));
+
+    // unless the migrate msg replaces them
+    let game_addr = create_legacy(
+        &mut app,
+        &netwars_admin,
+        LegacyInstantiateMsg {
+            archid: Some(Archid {
+                registry: Some(Addr::unchecked("archid_registry")),
+                cw721: Some(Addr::unchecked("archid_cw721")),
+            }),
+            ..legacy_msg("0.1.4")
+        },
+        &pot,
+    );
+    let msg = MigrateMsg {
+        archid_cw721: Some("new_archid_cw721".to_string()),
+        ..migrate_msg()
+    };
+    let _res = app
+        .migrate_contract(netwars_admin.clone(), game_addr.clone(), &msg, netwars_id)
+        .unwrap();
+    let config: Config = query(&mut app, game_addr.clone(), QueryMsg::Config{ game_id: 1 }).unwrap();
+    assert_eq!(config.archid_registry, Some(Addr::unchecked("archid_registry")));
+    assert_eq!(config.eligibility, EligibilityRule::ArchId { cw721: Addr::unchecked("new_archid_cw721") });
+
+    // 0.1.4 games without ArchID contracts are open to anyone,
+    // game play continues where it left off
+    let game_addr = create_legacy(
+        &mut app,
+        &netwars_admin,
+        LegacyInstantiateMsg {
+            archid: Some(Archid { registry: None, cw721: None }),
+            ..legacy_msg("0.1.4")
+        },
+        &pot,
+    );
+    // the round is counted from the upgrade
+    let migrated_at = get_block_time(&mut app) + 10;
+    increment_block_time(&mut app, migrated_at, 2);
+    let _res = app
+        .migrate_contract(netwars_admin.clone(), game_addr.clone(), &migrate_msg(), netwars_id)
+        .unwrap();
+    let config: Config = query(&mut app, game_addr.clone(), QueryMsg::Config{ game_id: 1 }).unwrap();
+    assert_eq!(config.eligibility, EligibilityRule::None {});
+    let _res = app
+        .execute_contract(
+            depositor.clone(),
+            game_addr.clone(),
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None },
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        )
+        .unwrap();
+    let game_query: RoundState = query(&mut app, game_addr.clone(), QueryMsg::CurrentRound{ game_id: 1 }).unwrap();
+    assert_eq!(game_query.round, 4_u64);
+    assert_eq!(game_query.round_start, migrated_at);
+    assert_eq!(game_query.deposits, 1_u64);
+    assert_eq!(game_query.last_depositor, Some(depositor.clone()));
+    let status: StatusResponse = query(&mut app, game_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(status.pot, Uint128::from(3000000000000000000_u128));
+
+    // the denom played by legacy games must be given
+    let game_addr = create_legacy(&mut app, &netwars_admin, legacy_msg("0.1.4"), &[]);
+    let msg = MigrateMsg {
+        legacy_denom: None,
+        ..migrate_msg()
+    };
+    let err = app
+        .migrate_contract(netwars_admin.clone(), game_addr.clone(), &msg, netwars_id)
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        ContractError::MissingValue { field: "legacy_denom".to_string() }.to_string()
+    );
+
+    // pausing can be required before upgrading
+    let game_addr = create_legacy(&mut app, &netwars_admin, legacy_msg("0.1.4"), &[]);
+    let msg = MigrateMsg {
+        require_paused: Some(true),
+        ..migrate_msg()
+    };
+    let err = app
+        .migrate_contract(netwars_admin.clone(), game_addr.clone(), &msg, netwars_id)
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), ContractError::NotPaused {}.to_string());
+    let game_addr = create_legacy(
+        &mut app,
+        &netwars_admin,
+        LegacyInstantiateMsg { paused: true, ..legacy_msg("0.1.4") },
+        &[],
+    );
+    let _res = app
+        .migrate_contract(netwars_admin.clone(), game_addr.clone(), &msg, netwars_id)
+        .unwrap();
+    let game_query: RoundState = query(&mut app, game_addr.clone(), QueryMsg::CurrentRound{ game_id: 1 }).unwrap();
+    assert!(game_query.is_paused());
+
+    // versions are compared as semver (0.10.0 is newer than 0.3.1)
+    let game_addr = create_legacy(&mut app, &netwars_admin, legacy_msg("0.10.0"), &[]);
+    let err = app
+        .migrate_contract(netwars_admin.clone(), game_addr.clone(), &migrate_msg(), netwars_id)
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        ContractError::CannotMigrateVersion {
+            from: "0.10.0".to_string(),
+            to: env!("CARGO_PKG_VERSION").to_string(),
+        }.to_string()
+    );
+
+    // other contracts can't be migrated
+    let game_addr = create_legacy(
+        &mut app,
+        &netwars_admin,
+        LegacyInstantiateMsg { contract: "other-game".to_string(), ..legacy_msg("0.1.0") },
+        &[],
+    );
+    let err = app
+        .migrate_contract(netwars_admin.clone(), game_addr, &migrate_msg(), netwars_id)
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        ContractError::CannotMigrateContract { contract: "other-game".to_string() }.to_string()
+    );
+
+    // games can't be migrated to the version they're on
+    let game_addr = create_legacy(&mut app, &netwars_admin, legacy_msg(env!("CARGO_PKG_VERSION")), &[]);
+    let err = app
+        .migrate_contract(netwars_admin.clone(), game_addr, &migrate_msg(), netwars_id)
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        ContractError::CannotMigrateVersion {
+            from: env!("CARGO_PKG_VERSION").to_string(),
+            to: env!("CARGO_PKG_VERSION").to_string(),
+        }.to_string()
+    );
+}
+
+// A round in progress on 0.1.4 keeps its leader, who
+// can still claim the prize after upgrading
+#[test]
+fn test_migrate_active_round() {
+    let mut app = mock_app();
+
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // depositor is leading the round when the game is upgraded
+    let depositor = Addr::unchecked("arch_owner");
+    mint_native(
+        &mut app,
+        netwars_admin.to_string(),
+        Uint128::from(10000000000000000000_u128), // 10 ARCH as aarch
+    );
+    let netwars_id = app.store_code(contract_netwars());
+
+    // 0.1.4 state and ArchID items, with 3 ARCH in the pot
+    let now = get_block_time(&mut app);
+    let state = format!(
+        r#"{{"owner":"{}","expiration":{},"min_deposit":"1000000000000000000","last_deposit":{},"last_depositor":"{}","extensions":30,"stale":604800,"reset_length":604800,"round":2,"paused":null}}"#,
+        netwars_admin,
+        now + 120,
+        now,
+        depositor,
+    );
+    let archid = r#"{"registry":null,"cw721":null}"#.to_string();
+    let game_addr = create_raw(
+        &mut app,
+        &netwars_admin,
+        RawInstantiateMsg {
+            version: "0.1.4".to_string(),
+            items: vec![("state".to_string(), state), ("archid".to_string(), archid)],
+        },
+        &[Coin {
+            denom: String::from(DENOM),
+            amount: Uint128::from(3000000000000000000_u128)
+        }],
+    );
+    let _res = app
+        .migrate_contract(netwars_admin.clone(), game_addr.clone(), &migrate_msg(), netwars_id)
+        .unwrap();
+
+    // depositor is still the leader
+    let game_query: RoundState = query(&mut app, game_addr.clone(), QueryMsg::CurrentRound{ game_id: 1 }).unwrap();
+    assert_eq!(game_query.round, 2_u64);
+    assert_eq!(game_query.last_depositor, Some(depositor.clone()));
+
+    // and wins the round once it expires
+    let current_time = get_block_time(&mut app);
+    increment_block_time(&mut app, current_time + 300, 7);
+    let _res = app
+        .execute_contract(
+            depositor.clone(),
+            game_addr.clone(),
+            &ExecuteMsg::Claim{ game_id: 1 },
+            &[]
+        )
+        .unwrap();
+    let winner_balance: Coin = bank_query(&mut app, &depositor);
+    assert_eq!(winner_balance.amount, Uint128::from(3000000000000000000_u128));
+    let game_query: RoundState = query(&mut app, game_addr, QueryMsg::CurrentRound{ game_id: 1 }).unwrap();
+    assert_eq!(game_query.round, 3_u64);
+    assert_eq!(game_query.last_depositor, None);
+}
+
+// A 0.1.4 round unlocked without any deposits since has no
+// leader, the unlocker can't claim it after upgrading
+#[test]
+fn test_migrate_unlocked_round() {
+    let mut app = mock_app();
+
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // unlocker reset the round on 0.1.4
+    let unlocker = Addr::unchecked("unlocker");
+    mint_native(
+        &mut app,
+        netwars_admin.to_string(),
+        Uint128::from(10000000000000000000_u128), // 10 ARCH as aarch
+    );
+    let netwars_id = app.store_code(contract_netwars());
+
+    // 0.1.4 state item as left by unlocking, with 3 ARCH in the pot
+    let now = get_block_time(&mut app);
+    let state = format!(
+        r#"{{"owner":"{}","expiration":{},"min_deposit":"1000000000000000000","last_deposit":{},"last_depositor":"{}","extensions":30,"stale":604800,"reset_length":604800,"round":5,"paused":null}}"#,
+        netwars_admin,
+        now + 604800,
+        now,
+        unlocker,
+    );
+    let game_addr = create_raw(
+        &mut app,
+        &netwars_admin,
+        RawInstantiateMsg {
+            version: "0.1.4".to_string(),
+            items: vec![("state".to_string(), state)],
+        },
+        &[Coin {
+            denom: String::from(DENOM),
+            amount: Uint128::from(3000000000000000000_u128)
+        }],
+    );
+    let _res = app
+        .migrate_contract(netwars_admin.clone(), game_addr.clone(), &migrate_msg(), netwars_id)
+        .unwrap();
+
+    // the round has no leader
+    let game_query: RoundState = query(&mut app, game_addr.clone(), QueryMsg::CurrentRound{ game_id: 1 }).unwrap();
+    assert_eq!(game_query.round, 5_u64);
+    assert_eq!(game_query.last_depositor, None);
+
+    // so the unlocker can't claim the pot once it expires
+    increment_block_time(&mut app, now + 604801, 7);
+    let err = app
+        .execute_contract(
+            unlocker.clone(),
+            game_addr.clone(),
+            &ExecuteMsg::Claim{ game_id: 1 },
+            &[]
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), ContractError::Unauthorized {}.to_string());
+    let status: StatusResponse = query(&mut app, game_addr, QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(status.pot, Uint128::from(3000000000000000000_u128));
+}
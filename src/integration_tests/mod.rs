@@ -1,7 +1,30 @@
 pub mod util;
 
 mod archid;
+mod archid_expiry;
+mod bulk;
 mod claim;
+mod claim_deadline;
+mod cw20_deposit;
 mod deposit;
+mod dividends;
+mod eligibility;
+mod fees;
+mod funds;
+mod games;
+mod keeper;
+mod migrate;
+mod ownership;
 mod pause;
-mod stale;
\ No newline at end of file
+mod players;
+mod price;
+mod referrals;
+mod roles;
+mod rounds;
+mod sponsors;
+mod stale;
+mod status;
+mod sweep;
+mod timelock;
+mod timer;
+mod validation;
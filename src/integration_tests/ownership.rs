@@ -0,0 +1,221 @@
+#![cfg(test)]
+use cosmwasm_std::{
+    Addr, Uint128,
+};
+use cw_multi_test::Executor;
+
+use crate::integration_tests::util::{
+    create_netwars, get_block_time, increment_block_time, mock_app, query,
+};
+
+use crate::msg::{
+    ExecuteMsg, OwnershipResponse, QueryMsg,
+};
+
+// Ownership moves only when the proposed owner accepts
+// it, and can be renounced for good
+#[test]
+fn test_ownership_transfer() {
+    let mut app = mock_app();
+    
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // new_admin will take over the game
+    let new_admin = Addr::unchecked("new_admin");
+    // random has no rights
+    let random = Addr::unchecked("random");
+
+    let netwars_addr: Addr = create_netwars(
+        &mut app, 
+        &netwars_admin, 
+        None,
+        None,
+        120, // 2 minutes
+        Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+        30, // 30 seconds
+        604800, // ~1 week
+        604800, // ~1 week
+        &[],
+    );
+
+    let propose_msg = ExecuteMsg::ProposeOwner { 
+        new_owner: new_admin.to_string(), 
+        expiry: Some(100),
+    };
+
+    // random can't propose a new owner
+    assert!(
+        app.execute_contract(
+            random.clone(), 
+            netwars_addr.clone(), 
+            &propose_msg, 
+            &[]
+        ).is_err()
+    );
+
+    // proposed owner must be a valid address
+    assert!(
+        app.execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::ProposeOwner { new_owner: String::from("Not Valid"), expiry: None }, 
+            &[]
+        ).is_err()
+    );
+
+    // admin proposes new_admin
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &propose_msg, 
+            &[]
+        )
+        .unwrap();
+    let current_time = get_block_time(&mut app);
+    let res: OwnershipResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Ownership{},
+    ).unwrap();
+    assert_eq!(res, OwnershipResponse {
+        owner: Some(netwars_admin.clone()),
+        pending_owner: Some(new_admin.clone()),
+        pending_expiry: Some(current_time + 100),
+    });
+
+    // only the proposed owner can accept
+    assert!(
+        app.execute_contract(
+            random.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::AcceptOwnership{}, 
+            &[]
+        ).is_err()
+    );
+
+    // admin cancels the proposal
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::CancelOwnershipProposal{}, 
+            &[]
+        )
+        .unwrap();
+    assert!(
+        app.execute_contract(
+            new_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::AcceptOwnership{}, 
+            &[]
+        ).is_err()
+    );
+
+    // expired proposals can't be accepted
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &propose_msg, 
+            &[]
+        )
+        .unwrap();
+    increment_block_time(&mut app, current_time + 100, 7);
+    assert!(
+        app.execute_contract(
+            new_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::AcceptOwnership{}, 
+            &[]
+        ).is_err()
+    );
+
+    // new_admin accepts a proposal without expiry
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::ProposeOwner { new_owner: new_admin.to_string(), expiry: None }, 
+            &[]
+        )
+        .unwrap();
+    let _res = app
+        .execute_contract(
+            new_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::AcceptOwnership{}, 
+            &[]
+        )
+        .unwrap();
+    let res: OwnershipResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Ownership{},
+    ).unwrap();
+    assert_eq!(res, OwnershipResponse {
+        owner: Some(new_admin.clone()),
+        pending_owner: None,
+        pending_expiry: None,
+    });
+
+    // previous admin lost admin rights
+    assert!(
+        app.execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Pause{ game_id: 1 }, 
+            &[]
+        ).is_err()
+    );
+    let _res = app
+        .execute_contract(
+            new_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Pause{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+    let _res = app
+        .execute_contract(
+            new_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Unpause{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+
+    // new_admin renounces ownership
+    let _res = app
+        .execute_contract(
+            new_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::RenounceOwnership{}, 
+            &[]
+        )
+        .unwrap();
+    let res: OwnershipResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Ownership{},
+    ).unwrap();
+    assert_eq!(res.owner, None);
+
+    // nobody can act as admin anymore
+    assert!(
+        app.execute_contract(
+            new_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Pause{ game_id: 1 }, 
+            &[]
+        ).is_err()
+    );
+    assert!(
+        app.execute_contract(
+            new_admin, 
+            netwars_addr, 
+            &propose_msg, 
+            &[]
+        ).is_err()
+    );
+}
@@ -12,8 +12,8 @@ use crate::integration_tests::util::{
 use crate::msg::{
     ExecuteMsg, QueryMsg,
 };
-use crate::contract::DENOM;
-use crate::state::{State};
+use crate::integration_tests::util::DENOM;
+use crate::state::{RoundState};
 
 // Only admin can pause. When admin pauses game, 
 // players cannot deposit or claim prizes until 
@@ -75,7 +75,7 @@ fn test_pause_unpause() {
         .execute_contract(
             first_depositor.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Deposit{}, 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
             &[Coin {
                 denom: String::from(DENOM),
                 amount: Uint128::from(1000000000000000000_u128)
@@ -88,7 +88,7 @@ fn test_pause_unpause() {
         .execute_contract(
             second_depositor.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Deposit{}, 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
             &[Coin {
                 denom: String::from(DENOM),
                 amount: Uint128::from(1000000000000000000_u128)
@@ -101,7 +101,7 @@ fn test_pause_unpause() {
         .execute_contract(
             netwars_admin.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Pause{}, 
+            &ExecuteMsg::Pause{ game_id: 1 }, 
             &[]
         ).is_ok()
     );
@@ -111,7 +111,7 @@ fn test_pause_unpause() {
         .execute_contract(
             first_depositor.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Deposit{}, 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
             &[Coin {
                 denom: String::from(DENOM),
                 amount: Uint128::from(1000000000000000000_u128)
@@ -123,7 +123,7 @@ fn test_pause_unpause() {
         .execute_contract(
             second_depositor.clone(),
             netwars_addr.clone(), 
-            &ExecuteMsg::Deposit{}, 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
             &[Coin {
                 denom: String::from(DENOM),
                 amount: Uint128::from(1000000000000000000_u128)
@@ -136,7 +136,7 @@ fn test_pause_unpause() {
         .execute_contract(
             netwars_admin.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Unpause{},
+            &ExecuteMsg::Unpause{ game_id: 1 },
             &[]
         ).is_ok()
     );
@@ -152,7 +152,7 @@ fn test_pause_unpause() {
         app.execute_contract(
             first_depositor.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Deposit{}, 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
             &[Coin {
                 denom: String::from(DENOM),
                 amount: Uint128::from(1000000000000000000_u128)
@@ -165,7 +165,7 @@ fn test_pause_unpause() {
         .execute_contract(
             first_depositor.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Claim{}, 
+            &ExecuteMsg::Claim{ game_id: 1 }, 
             &[]
         )
         .is_err()
@@ -176,7 +176,7 @@ fn test_pause_unpause() {
         .execute_contract(
             first_depositor.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Pause{}, 
+            &ExecuteMsg::Pause{ game_id: 1 }, 
             &[]
         ).is_err()
     );
@@ -186,7 +186,7 @@ fn test_pause_unpause() {
         .execute_contract(
             netwars_admin.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Pause{}, 
+            &ExecuteMsg::Pause{ game_id: 1 }, 
             &[]
         ).is_ok()
     );
@@ -196,7 +196,7 @@ fn test_pause_unpause() {
         .execute_contract(
             second_depositor.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Claim{}, 
+            &ExecuteMsg::Claim{ game_id: 1 }, 
             &[]
         ).is_err()
     );
@@ -206,7 +206,7 @@ fn test_pause_unpause() {
         .execute_contract(
             first_depositor.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Unpause{}, 
+            &ExecuteMsg::Unpause{ game_id: 1 }, 
             &[]
         ).is_err()
     );
@@ -216,7 +216,7 @@ fn test_pause_unpause() {
         .execute_contract(
             netwars_admin.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Unpause{},
+            &ExecuteMsg::Unpause{ game_id: 1 },
             &[]
         ).is_ok()
     );
@@ -227,7 +227,7 @@ fn test_pause_unpause() {
         .execute_contract(
             second_depositor.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Claim{}, 
+            &ExecuteMsg::Claim{ game_id: 1 }, 
             &[]
         )
         .unwrap();
@@ -243,10 +243,10 @@ fn test_pause_unpause() {
 
     // game was correctly restarted, and
     // round was increased
-    let game_query: State = query(
+    let game_query: RoundState = query(
         &mut app,
         netwars_addr.clone(),
-        QueryMsg::Game{},
+        QueryMsg::CurrentRound{ game_id: 1 },
     ).unwrap();
     assert_eq!(game_query.round, 2_u64);
 
@@ -256,13 +256,14 @@ fn test_pause_unpause() {
         .execute_contract(
             first_depositor, 
             netwars_addr.clone(), 
-            &ExecuteMsg::Deposit{}, 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
             &[Coin {
                 denom: String::from(DENOM),
                 amount: Uint128::from(2000000000000000000_u128)
             }]
         )
         .unwrap();
+    // amounts above the price are refunded
     let netwars_balance: Coin = bank_query(&mut app, &netwars_addr);
-    assert_eq!(netwars_balance.amount, Uint128::from(2000000000000000000_u128));
+    assert_eq!(netwars_balance.amount, Uint128::from(1000000000000000000_u128));
 }
\ No newline at end of file
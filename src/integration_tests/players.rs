@@ -0,0 +1,200 @@
+#![cfg(test)]
+use cosmwasm_std::{
+    Addr, Coin, Uint128,
+};
+use cw_multi_test::Executor;
+
+use crate::integration_tests::util::{
+    create_netwars, increment_block_time, get_block_time, mint_native, mock_app, query,
+};
+
+use crate::msg::{
+    ExecuteMsg, PlayerResponse, PlayersResponse, QueryMsg,
+};
+use crate::integration_tests::util::DENOM;
+use crate::state::{PlayerRoundStats, PlayerStats};
+
+// Deposits must be credited to the depositor's ledger, 
+// both for the current round and for the lifetime of
+// the game
+#[test]
+fn test_player_stats() {
+    let mut app = mock_app();
+    
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // first_depositor owns ARCH
+    let first_depositor = Addr::unchecked("arch_owner");
+    // second_depositor owns ARCH
+    let second_depositor = Addr::unchecked("second_arch_owner");
+
+    // mint arch to first_depositor and second_depositor
+    mint_native(
+        &mut app,
+        first_depositor.to_string(),
+        Uint128::from(100000000000000000000_u128), // 100 ARCH as aarch
+    );
+    mint_native(
+        &mut app,
+        second_depositor.to_string(),
+        Uint128::from(100000000000000000000_u128), // 100 ARCH as aarch
+    );
+    
+    // contract settings
+    let expiration: u64 = 120; // 2 minutes
+    let min_deposit =  Uint128::from(1000000000000000000_u128); // 1 ARCH as aarch
+    let extension_length: u64 = 30; // 30 seconds
+    let stale: u64 = 604800; // ~1 week
+    let reset_length: u64 = 604800; // ~1 week
+
+    // netwars_admin creates the netwars contract 
+    let netwars_addr: Addr = create_netwars(
+        &mut app, 
+        &netwars_admin, 
+        None,
+        None,
+        expiration, 
+        min_deposit,
+        extension_length,
+        stale,
+        reset_length,
+        &[],
+    );
+
+    // first_depositor makes two deposits
+    for _ in 0..2 {
+        let _res = app
+            .execute_contract(
+                first_depositor.clone(), 
+                netwars_addr.clone(), 
+                &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
+                &[Coin {
+                    denom: String::from(DENOM),
+                    amount: Uint128::from(1000000000000000000_u128)
+                }]
+            )
+            .unwrap();
+    }
+
+    // second_depositor buys three units in one deposit
+    let _res = app
+        .execute_contract(
+            second_depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: Some(3), referrer: None }, 
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(3000000000000000000_u128)
+            }]
+        )
+        .unwrap();
+
+    // player ledger defaults to the current round
+    let res: PlayerResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Player { game_id: 1, address: first_depositor.to_string(), round: None },
+    ).unwrap();
+    assert_eq!(res.round, 1_u64);
+    assert_eq!(res.round_stats, PlayerRoundStats {
+        deposits: 2,
+        total: Uint128::from(2000000000000000000_u128),
+    });
+
+    // every unit is credited
+    let res: PlayerResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Player { game_id: 1, address: second_depositor.to_string(), round: Some(1) },
+    ).unwrap();
+    assert_eq!(res.round_stats, PlayerRoundStats {
+        deposits: 3,
+        total: Uint128::from(3000000000000000000_u128),
+    });
+
+    // all depositors of the round can be listed
+    let res: PlayersResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Players { game_id: 1, round: None, start_after: None, limit: None },
+    ).unwrap();
+    assert_eq!(res.players.len(), 2);
+    assert_eq!(res.players[0].address, first_depositor);
+    assert_eq!(res.players[1].address, second_depositor);
+
+    let res: PlayersResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Players { 
+            game_id: 1, 
+            round: Some(1), 
+            start_after: Some(first_depositor.to_string()), 
+            limit: Some(1),
+        },
+    ).unwrap();
+    assert_eq!(res.players.len(), 1);
+    assert_eq!(res.players[0].address, second_depositor);
+
+    // second_depositor wins and claims round 1
+    let current_time = get_block_time(&mut app);
+    increment_block_time(&mut app, current_time + 1000, 7);
+    let _res = app
+        .execute_contract(
+            second_depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Claim{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+
+    // first_depositor makes a deposit to round 2
+    let _res = app
+        .execute_contract(
+            first_depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        )
+        .unwrap();
+
+    // round ledger only counts round 2, lifetime 
+    // ledger counts both rounds
+    let res: PlayerResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Player { game_id: 1, address: first_depositor.to_string(), round: None },
+    ).unwrap();
+    assert_eq!(res.round, 2_u64);
+    assert_eq!(res.round_stats, PlayerRoundStats {
+        deposits: 1,
+        total: Uint128::from(1000000000000000000_u128),
+    });
+    assert_eq!(res.lifetime_stats, PlayerStats {
+        deposits: 3,
+        total: Uint128::from(3000000000000000000_u128),
+        rounds: 2,
+        ..PlayerStats::default()
+    });
+
+    // round 2 only has one depositor so far
+    let res: PlayersResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Players { game_id: 1, round: None, start_after: None, limit: None },
+    ).unwrap();
+    assert_eq!(res.round, 2_u64);
+    assert_eq!(res.players.len(), 1);
+    assert_eq!(res.players[0].address, first_depositor);
+
+    // players who never deposited have an empty ledger
+    let res: PlayerResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Player { game_id: 1, address: netwars_admin.to_string(), round: None },
+    ).unwrap();
+    assert_eq!(res.round_stats, PlayerRoundStats::default());
+    assert_eq!(res.lifetime_stats, PlayerStats::default());
+}
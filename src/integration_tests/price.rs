@@ -0,0 +1,236 @@
+#![cfg(test)]
+use cosmwasm_std::{
+    Addr, Coin, Uint128,
+};
+use cw_multi_test::Executor;
+
+use crate::integration_tests::util::{
+    bank_query, increment_block_time, get_block_time, instantiate_msg, instantiate_netwars, mint_native, 
+    mock_app, query, DENOM,
+};
+
+use crate::msg::{
+    ConfigureMsg, ExecuteMsg, InstantiateMsg, PriceResponse, QueryMsg, StatusResponse,
+};
+use crate::state::{PriceCurve, PriceStep, MAX_GROWTH_BPS};
+
+// Deposit price must follow the configured curve and
+// reset to min_deposit when a new round starts
+#[test]
+fn test_price_curve() {
+    let mut app = mock_app();
+    
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // depositor owns ARCH
+    let depositor = Addr::unchecked("arch_owner");
+
+    // mint arch to depositor
+    mint_native(
+        &mut app,
+        depositor.to_string(),
+        Uint128::from(100000000000000000000_u128), // 100 ARCH as aarch
+    );
+
+    // price increases by 0.5 ARCH per deposit
+    let msg = InstantiateMsg {
+        price_curve: Some(PriceCurve::Linear { 
+            increment: Uint128::from(500000000000000000_u128),
+        }),
+        ..instantiate_msg()
+    };
+    let netwars_addr: Addr = instantiate_netwars(&mut app, &netwars_admin, &msg, &[]);
+
+    // first deposit costs min_deposit
+    let res: PriceResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::CurrentPrice{ game_id: 1 },
+    ).unwrap();
+    assert_eq!(res.price, Uint128::from(1000000000000000000_u128));
+    let _res = app
+        .execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        )
+        .unwrap();
+
+    // second deposit costs 1.5 ARCH
+    let res: PriceResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::CurrentPrice{ game_id: 1 },
+    ).unwrap();
+    assert_eq!(res.price, Uint128::from(1500000000000000000_u128));
+    assert!(
+        app.execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128) // Invalid amount (less than price)
+            }]
+        ).is_err()
+    );
+    let _res = app
+        .execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1500000000000000000_u128)
+            }]
+        )
+        .unwrap();
+    let netwars_balance: Coin = bank_query(&mut app, &netwars_addr);
+    assert_eq!(netwars_balance.amount, Uint128::from(2500000000000000000_u128));
+
+    // steps must be ordered
+    let mut config_msg = ConfigureMsg {
+        price_curve: Some(PriceCurve::Steps {
+            steps: vec![
+                PriceStep { deposits: 5, price: Uint128::from(2000000000000000000_u128) },
+                PriceStep { deposits: 1, price: Uint128::from(3000000000000000000_u128) },
+            ],
+        }),
+        ..Default::default()
+    };
+    assert!(
+        app.execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Configure { game_id: 1, msg: config_msg.clone() }, 
+            &[]
+        ).is_err()
+    );
+
+    // admin switches to 10% growth per deposit
+    config_msg.price_curve = Some(PriceCurve::Exponential { growth_bps: 1000 });
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Configure { game_id: 1, msg: config_msg.clone() }, 
+            &[]
+        )
+        .unwrap();
+
+    // 2 deposits were made, price is 1 ARCH * 1.1^2
+    let res: PriceResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::CurrentPrice{ game_id: 1 },
+    ).unwrap();
+    assert_eq!(res.price, Uint128::from(1210000000000000000_u128));
+
+    // depositor wins the round
+    let current_time = get_block_time(&mut app);
+    increment_block_time(&mut app, current_time + 1000, 7);
+    let _res = app
+        .execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Claim{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+
+    // price is reset for the new round
+    let res: PriceResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::CurrentPrice{ game_id: 1 },
+    ).unwrap();
+    assert_eq!(res.price, Uint128::from(1000000000000000000_u128));
+
+    // admin switches to a step schedule
+    config_msg.price_curve = Some(PriceCurve::Steps {
+        steps: vec![
+            PriceStep { deposits: 1, price: Uint128::from(2000000000000000000_u128) },
+            PriceStep { deposits: 3, price: Uint128::from(5000000000000000000_u128) },
+        ],
+    });
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Configure { game_id: 1, msg: config_msg.clone() }, 
+            &[]
+        )
+        .unwrap();
+
+    // price follows the schedule
+    let mut expected_prices: Vec<u128> = vec![
+        1000000000000000000, 
+        2000000000000000000, 
+        2000000000000000000, 
+        5000000000000000000,
+    ];
+    for expected_price in expected_prices.drain(..) {
+        let res: PriceResponse = query(
+            &mut app,
+            netwars_addr.clone(),
+            QueryMsg::CurrentPrice{ game_id: 1 },
+        ).unwrap();
+        assert_eq!(res.price, Uint128::from(expected_price));
+        let _res = app
+            .execute_contract(
+                depositor.clone(), 
+                netwars_addr.clone(), 
+                &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
+                &[Coin {
+                    denom: String::from(DENOM),
+                    amount: res.price
+                }]
+            )
+            .unwrap();
+    }
+
+    // growth is capped at 10% per deposit
+    config_msg.price_curve = Some(PriceCurve::Exponential { growth_bps: MAX_GROWTH_BPS + 1 });
+    assert!(
+        app.execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Configure { game_id: 1, msg: config_msg.clone() }, 
+            &[]
+        ).is_err()
+    );
+
+    // admin switches to 10% growth with a huge min_deposit
+    config_msg.price_curve = Some(PriceCurve::Exponential { growth_bps: MAX_GROWTH_BPS });
+    config_msg.min_deposit = Some(Uint128::from(300000000000000000000000000000000000000_u128));
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Configure { game_id: 1, msg: config_msg }, 
+            &[]
+        )
+        .unwrap();
+
+    // deposits priced beyond Uint128 are rejected
+    assert!(
+        app.execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        ).is_err()
+    );
+
+    // but the game status is still reported
+    let res: StatusResponse = query(&mut app, netwars_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(res.round, 2);
+    assert_eq!(res.price, None);
+}
@@ -0,0 +1,181 @@
+#![cfg(test)]
+use cosmwasm_std::{
+    Addr, Coin, Uint128,
+};
+use cw_multi_test::{App, Executor};
+
+use crate::integration_tests::util::{
+    bank_query, contract_netwars, get_block_time, increment_block_time, instantiate_msg, instantiate_netwars,
+    mint_native, mock_app, query,
+};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, QueryMsg, ReferralsResponse,
+};
+use crate::integration_tests::util::DENOM;
+
+fn deposit(app: &mut App, depositor: &Addr, contract: &Addr, referrer: Option<&Addr>) -> Result<(), String> {
+    app.execute_contract(
+        depositor.clone(),
+        contract.clone(),
+        &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: referrer.map(|addr| addr.to_string()) },
+        &[Coin {
+            denom: String::from(DENOM),
+            amount: Uint128::from(1000000000000000000_u128)
+        }]
+    )
+    .map(|_| ())
+    .map_err(|err| err.root_cause().to_string())
+}
+
+// Referrers earn a cut of the deposits made by the players
+// they referred, which is kept out of the prize pool until
+// they withdraw it
+#[test]
+fn test_referrals() {
+    let mut app = mock_app();
+
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // referrer owns ARCH and refers player
+    let referrer = Addr::unchecked("arch_owner");
+    // player owns ARCH
+    let player = Addr::unchecked("second_arch_owner");
+    // newcomer has never deposited
+    let newcomer = Addr::unchecked("newcomer");
+
+    // mint arch to referrer and player
+    mint_native(
+        &mut app,
+        referrer.to_string(),
+        Uint128::from(10000000000000000000_u128), // 10 ARCH as aarch
+    );
+    mint_native(
+        &mut app,
+        player.to_string(),
+        Uint128::from(10000000000000000000_u128), // 10 ARCH as aarch
+    );
+
+    // fees and referral rewards can't exceed 100% together
+    let mut msg = InstantiateMsg {
+        fee_bps: Some(5000),
+        referral_bps: Some(6000),
+        ..instantiate_msg()
+    };
+    let netwars_id = app.store_code(contract_netwars());
+    assert!(
+        app.instantiate_contract(netwars_id, netwars_admin.clone(), &msg, &[], "Netwars", None)
+            .is_err()
+    );
+
+    // 5% fee and 10% referral reward
+    msg.fee_bps = Some(500);
+    msg.referral_bps = Some(1000);
+    let netwars_addr: Addr = instantiate_netwars(&mut app, &netwars_admin, &msg, &[]);
+
+    // referrer deposits without being referred
+    deposit(&mut app, &referrer, &netwars_addr, None).unwrap();
+
+    // players can't refer themselves
+    assert_eq!(
+        deposit(&mut app, &player, &netwars_addr, Some(&player)).unwrap_err(),
+        ContractError::SelfReferral {}.to_string()
+    );
+
+    // referrers must have made a deposit
+    assert_eq!(
+        deposit(&mut app, &player, &netwars_addr, Some(&newcomer)).unwrap_err(),
+        ContractError::InvalidReferrer {}.to_string()
+    );
+
+    // player deposits with a referrer
+    deposit(&mut app, &player, &netwars_addr, Some(&referrer)).unwrap();
+    let res: ReferralsResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Referrals { game_id: 1, address: referrer.to_string() },
+    ).unwrap();
+    assert_eq!(res.pending, Uint128::from(100000000000000000_u128));
+
+    // player registers referrer, which can only be done once
+    let _res = app
+        .execute_contract(
+            player.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::SetReferrer{ game_id: 1, referrer: referrer.to_string() },
+            &[]
+        )
+        .unwrap();
+    let err = app
+        .execute_contract(
+            player.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::SetReferrer{ game_id: 1, referrer: referrer.to_string() },
+            &[]
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), ContractError::ReferrerAlreadySet {}.to_string());
+    let res: ReferralsResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Referrals { game_id: 1, address: player.to_string() },
+    ).unwrap();
+    assert_eq!(res.referrer, Some(referrer.clone()));
+
+    // registered referrer is credited whatever referrer
+    // is sent with a deposit
+    deposit(&mut app, &player, &netwars_addr, Some(&newcomer)).unwrap();
+    let res: ReferralsResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Referrals { game_id: 1, address: referrer.to_string() },
+    ).unwrap();
+    assert_eq!(res.pending, Uint128::from(200000000000000000_u128));
+    assert_eq!(res.withdrawn, Uint128::zero());
+
+    // player wins 3 ARCH deposited less 0.15 ARCH fees
+    // and 0.2 ARCH referral rewards
+    let current_time = get_block_time(&mut app);
+    increment_block_time(&mut app, current_time + 1000, 7);
+    let _res = app
+        .execute_contract(
+            player.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Claim{ game_id: 1 },
+            &[]
+        )
+        .unwrap();
+    let player_balance: Coin = bank_query(&mut app, &player);
+    assert_eq!(player_balance.amount, Uint128::from(10650000000000000000_u128));
+
+    // referrer withdraws referral rewards
+    let _res = app
+        .execute_contract(
+            referrer.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::WithdrawReferralEarnings{ game_id: 1 },
+            &[]
+        )
+        .unwrap();
+    let referrer_balance: Coin = bank_query(&mut app, &referrer);
+    assert_eq!(referrer_balance.amount, Uint128::from(9200000000000000000_u128));
+
+    // earnings can't be withdrawn twice
+    let err = app
+        .execute_contract(
+            referrer.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::WithdrawReferralEarnings{ game_id: 1 },
+            &[]
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), ContractError::NoReferralEarnings {}.to_string());
+    let res: ReferralsResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Referrals { game_id: 1, address: referrer.to_string() },
+    ).unwrap();
+    assert_eq!(res.pending, Uint128::zero());
+    assert_eq!(res.withdrawn, Uint128::from(200000000000000000_u128));
+}
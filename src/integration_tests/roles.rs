@@ -0,0 +1,248 @@
+#![cfg(test)]
+use cosmwasm_std::{
+    Addr, Uint128,
+};
+use cw_multi_test::Executor;
+
+use crate::integration_tests::util::{
+    create_netwars, mock_app, query,
+};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigureMsg, ExecuteMsg, QueryMsg, RoleMember, RolesResponse,
+};
+use crate::state::{Config, Role};
+
+// Admin can delegate pausing, configuration and fee
+// management to other addresses
+#[test]
+fn test_roles() {
+    let mut app = mock_app();
+    
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // ops holds the emergency pause key
+    let ops = Addr::unchecked("ops");
+    // configurator tunes game settings
+    let configurator = Addr::unchecked("configurator");
+
+    let netwars_addr: Addr = create_netwars(
+        &mut app, 
+        &netwars_admin, 
+        None,
+        None,
+        120, // 2 minutes
+        Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+        30, // 30 seconds
+        604800, // ~1 week
+        604800, // ~1 week
+        &[],
+    );
+
+    let config_msg = ExecuteMsg::Configure { 
+        game_id: 1, 
+        msg: ConfigureMsg {
+            extensions: Some(60),
+            ..Default::default()
+        },
+    };
+
+    // only admin can grant roles
+    assert!(
+        app.execute_contract(
+            ops.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::GrantRole { address: ops.to_string(), role: Role::Pauser }, 
+            &[]
+        ).is_err()
+    );
+
+    // owner role can't be granted
+    assert!(
+        app.execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::GrantRole { address: ops.to_string(), role: Role::Owner }, 
+            &[]
+        ).is_err()
+    );
+
+    // admin grants roles
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::GrantRole { address: ops.to_string(), role: Role::Pauser }, 
+            &[]
+        )
+        .unwrap();
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::GrantRole { address: configurator.to_string(), role: Role::Configurator }, 
+            &[]
+        )
+        .unwrap();
+
+    let res: RolesResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Roles { start_after: None, limit: None },
+    ).unwrap();
+    assert_eq!(res, RolesResponse {
+        owner: Some(netwars_admin.clone()),
+        members: vec![
+            RoleMember { address: configurator.clone(), roles: vec![Role::Configurator] },
+            RoleMember { address: ops.clone(), roles: vec![Role::Pauser] },
+        ],
+    });
+
+    // pauser can pause and unpause, but not configure
+    let _res = app
+        .execute_contract(
+            ops.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Pause{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+    let _res = app
+        .execute_contract(
+            ops.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Unpause{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+    assert!(
+        app.execute_contract(
+            ops.clone(), 
+            netwars_addr.clone(), 
+            &config_msg, 
+            &[]
+        ).is_err()
+    );
+
+    // configurator can configure, but not pause 
+    // or withdraw fees
+    let _res = app
+        .execute_contract(
+            configurator.clone(), 
+            netwars_addr.clone(), 
+            &config_msg, 
+            &[]
+        )
+        .unwrap();
+    assert!(
+        app.execute_contract(
+            configurator.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Pause{ game_id: 1 }, 
+            &[]
+        ).is_err()
+    );
+    assert!(
+        app.execute_contract(
+            configurator.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::WithdrawFees{ game_id: 1 }, 
+            &[]
+        ).is_err()
+    );
+
+    // fees are left to fee managers
+    let fee_msg = ExecuteMsg::Configure { 
+        game_id: 1, 
+        msg: ConfigureMsg {
+            fee_bps: Some(500),
+            fee_recipient: Some(configurator.to_string()),
+            ..Default::default()
+        },
+    };
+    let err = app
+        .execute_contract(
+            configurator.clone(), 
+            netwars_addr.clone(), 
+            &fee_msg, 
+            &[]
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), ContractError::Unauthorized {}.to_string());
+    let err = app
+        .execute_contract(
+            configurator.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Configure { 
+                game_id: 1, 
+                msg: ConfigureMsg {
+                    fee_recipient: Some(configurator.to_string()),
+                    ..Default::default()
+                },
+            }, 
+            &[]
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), ContractError::Unauthorized {}.to_string());
+    let config: Config = query(&mut app, netwars_addr.clone(), QueryMsg::Config{ game_id: 1 }).unwrap();
+    assert_eq!(config.fee_bps, 0);
+    assert_eq!(config.fee_recipient, None);
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &fee_msg, 
+            &[]
+        )
+        .unwrap();
+    let config: Config = query(&mut app, netwars_addr.clone(), QueryMsg::Config{ game_id: 1 }).unwrap();
+    assert_eq!(config.fee_bps, 500);
+
+    // admin revokes the pauser role
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::RevokeRole { address: ops.to_string(), role: Role::Pauser }, 
+            &[]
+        )
+        .unwrap();
+    assert!(
+        app.execute_contract(
+            ops.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Pause{ game_id: 1 }, 
+            &[]
+        ).is_err()
+    );
+
+    // roles that aren't held can't be revoked
+    assert!(
+        app.execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::RevokeRole { address: ops.to_string(), role: Role::Pauser }, 
+            &[]
+        ).is_err()
+    );
+
+    let res: RolesResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Roles { start_after: None, limit: None },
+    ).unwrap();
+    assert_eq!(res.members, vec![
+        RoleMember { address: configurator, roles: vec![Role::Configurator] },
+    ]);
+
+    // admin holds every role
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Pause{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+}
@@ -0,0 +1,208 @@
+#![cfg(test)]
+use cosmwasm_std::{
+    Addr, Coin, Uint128,
+};
+use cw_multi_test::Executor;
+
+use crate::integration_tests::util::{
+    create_netwars, increment_block_time, get_block_time, mint_native, mock_app, query,
+};
+
+use crate::msg::{
+    ExecuteMsg, QueryMsg, RoundsResponse,
+};
+use crate::integration_tests::util::DENOM;
+use crate::state::{RoundOutcome, RoundRecord, RoundState};
+
+// Claimed and stale rounds must be recorded in the 
+// round history, and the history must be queryable 
+// by round and by page
+#[test]
+fn test_round_history() {
+    let mut app = mock_app();
+    
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // depositor owns ARCH
+    let depositor = Addr::unchecked("arch_owner");
+    // complete_random owns nothing
+    let complete_random = Addr::unchecked("complete_random");
+
+    // mint arch to netwars_admin and depositor
+    mint_native(
+        &mut app,
+        netwars_admin.to_string(),
+        Uint128::from(15000000000000000000_u128), // 15 ARCH as aarch
+    );
+    mint_native(
+        &mut app,
+        depositor.to_string(),
+        Uint128::from(100000000000000000000_u128), // 100 ARCH as aarch
+    );
+    
+    // contract settings
+    let expiration: u64 = 120; // 2 minutes
+    let min_deposit =  Uint128::from(1000000000000000000_u128); // 1 ARCH as aarch
+    let extension_length: u64 = 30; // 30 seconds
+    let stale: u64 = 600; // 10 minutes
+    let reset_length: u64 = 600; // 10 minutes
+
+    // netwars_admin creates the netwars contract 
+    let netwars_addr: Addr = create_netwars(
+        &mut app, 
+        &netwars_admin, 
+        None,
+        None,
+        expiration, 
+        min_deposit,
+        extension_length,
+        stale,
+        reset_length,
+        &[Coin {
+            denom: String::from(DENOM),
+            amount: Uint128::from(15000000000000000000_u128)
+        }],
+    );
+    let round_1_start = get_block_time(&mut app);
+
+    // no rounds have been completed yet
+    assert!(
+        query::<QueryMsg, RoundRecord>(
+            &mut app,
+            netwars_addr.clone(),
+            QueryMsg::Round { game_id: 1, round: 1 },
+        ).is_err()
+    );
+
+    // depositor makes two deposits
+    for _ in 0..2 {
+        let _res = app
+            .execute_contract(
+                depositor.clone(), 
+                netwars_addr.clone(), 
+                &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
+                &[Coin {
+                    denom: String::from(DENOM),
+                    amount: Uint128::from(1000000000000000000_u128)
+                }]
+            )
+            .unwrap();
+    }
+    let round_1: RoundState = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::CurrentRound{ game_id: 1 },
+    ).unwrap();
+    assert_eq!(round_1.deposits, 2_u64);
+
+    // round 1 expires and depositor claims the prize
+    let current_time = get_block_time(&mut app);
+    increment_block_time(&mut app, current_time + 300, 7);
+    let _res = app
+        .execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Claim{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+    let round_2_start = get_block_time(&mut app);
+
+    // round 1 was recorded as claimed
+    // (seed funds + first deposit + second deposit)
+    let record: RoundRecord = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Round { game_id: 1, round: 1 },
+    ).unwrap();
+    assert_eq!(record, RoundRecord {
+        round: 1,
+        winner: Some(depositor.clone()),
+        winner_name: None,
+        prize: Uint128::from(17000000000000000000_u128),
+        start: round_1_start,
+        end: round_1.expiration,
+        deposits: 2,
+        deposited: Uint128::from(2000000000000000000_u128),
+        dividends: Uint128::zero(),
+        outcome: RoundOutcome::Claimed,
+        unlock_reward: Uint128::zero(),
+    });
+
+    // depositor makes a deposit to round 2
+    let _res = app
+        .execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        )
+        .unwrap();
+    let round_2: RoundState = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::CurrentRound{ game_id: 1 },
+    ).unwrap();
+
+    // round 2 expires, nobody claims and it becomes stale
+    let current_time = get_block_time(&mut app);
+    increment_block_time(&mut app, current_time + 5000, 10);
+    let _res = app
+        .execute_contract(
+            complete_random.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::UnlockStale{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+
+    // round 2 was recorded as stale, without a winner
+    let record: RoundRecord = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Round { game_id: 1, round: 2 },
+    ).unwrap();
+    assert_eq!(record, RoundRecord {
+        round: 2,
+        winner: None,
+        winner_name: None,
+        prize: Uint128::zero(),
+        start: round_2_start,
+        end: round_2.expiration,
+        deposits: 1,
+        deposited: Uint128::from(1000000000000000000_u128),
+        dividends: Uint128::zero(),
+        outcome: RoundOutcome::Stale,
+        unlock_reward: Uint128::zero(),
+    });
+
+    // round history can be paginated
+    let res: RoundsResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Rounds { game_id: 1, start_after: None, limit: None },
+    ).unwrap();
+    assert_eq!(res.rounds.len(), 2);
+    assert_eq!(res.rounds[0].round, 1_u64);
+    assert_eq!(res.rounds[1].round, 2_u64);
+
+    let res: RoundsResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Rounds { game_id: 1, start_after: Some(1), limit: Some(1) },
+    ).unwrap();
+    assert_eq!(res.rounds.len(), 1);
+    assert_eq!(res.rounds[0].outcome, RoundOutcome::Stale);
+
+    // round 3 has started with a fresh deposit count
+    let game_query: RoundState = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::CurrentRound{ game_id: 1 },
+    ).unwrap();
+    assert_eq!(game_query.round, 3_u64);
+    assert_eq!(game_query.deposits, 0_u64);
+}
@@ -0,0 +1,270 @@
+#![cfg(test)]
+use cosmwasm_std::{
+    Addr, Coin, Uint128,
+};
+use cw_multi_test::Executor;
+
+use crate::integration_tests::util::{
+    bank_query, create_netwars, increment_block_time, get_block_time,
+    mint_native, mock_app, query, DENOM,
+};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ExecuteMsg, QueryMsg, SponsorEntry, SponsorsResponse, StatusResponse, SurplusResponse,
+};
+use crate::state::{AssetInfo, MAX_FUND_AHEAD, RoundState};
+
+// Sponsors can top up the current or a future round
+// without taking part in the game
+#[test]
+fn test_sponsors() {
+    let mut app = mock_app();
+
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // depositor owns ARCH
+    let depositor = Addr::unchecked("arch_owner");
+    // sponsor owns ARCH
+    let sponsor = Addr::unchecked("sponsor");
+
+    mint_native(
+        &mut app,
+        netwars_admin.to_string(),
+        Uint128::from(5000000000000000000_u128), // 5 ARCH as aarch
+    );
+    for player in [&depositor, &sponsor] {
+        mint_native(
+            &mut app,
+            player.to_string(),
+            Uint128::from(10000000000000000000_u128), // 10 ARCH as aarch
+        );
+    }
+
+    // netwars_admin seeds the first round with 5 ARCH
+    let netwars_addr: Addr = create_netwars(
+        &mut app,
+        &netwars_admin,
+        None,
+        None,
+        120, // 2 minutes
+        Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+        30, // 30 seconds
+        604800, // ~1 week
+        604800, // ~1 week
+        &[Coin {
+            denom: String::from(DENOM),
+            amount: Uint128::from(5000000000000000000_u128)
+        }],
+    );
+    let res: SponsorsResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Sponsors{ game_id: 1, round: None, start_after: None, limit: None },
+    ).unwrap();
+    assert_eq!(res.round, 1);
+    assert_eq!(res.total, Uint128::from(5000000000000000000_u128));
+    assert_eq!(res.sponsors, vec![SponsorEntry {
+        address: netwars_admin.clone(),
+        amount: Uint128::from(5000000000000000000_u128),
+    }]);
+
+    let _res = app
+        .execute_contract(
+            depositor.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None },
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        )
+        .unwrap();
+    let round: RoundState = query(&mut app, netwars_addr.clone(), QueryMsg::CurrentRound{ game_id: 1 }).unwrap();
+
+    // empty contributions are rejected
+    let err = app
+        .execute_contract(
+            sponsor.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Fund{ game_id: 1, round: None },
+            &[]
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        ContractError::InsufficientFunds { required: None }.to_string()
+    );
+
+    // sponsor tops up the current round, which neither
+    // changes the leader nor extends the timer
+    let res = app
+        .execute_contract(
+            sponsor.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Fund{ game_id: 1, round: None },
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(2000000000000000000_u128)
+            }]
+        )
+        .unwrap();
+    let amount = res.events
+        .iter()
+        .flat_map(|e| e.attributes.iter())
+        .find(|a| a.key == "amount")
+        .unwrap();
+    assert_eq!(amount.value, "2000000000000000000");
+    let after: RoundState = query(&mut app, netwars_addr.clone(), QueryMsg::CurrentRound{ game_id: 1 }).unwrap();
+    assert_eq!(after, round);
+    let status: StatusResponse = query(&mut app, netwars_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(status.pot, Uint128::from(8000000000000000000_u128));
+
+    // and sponsors the next round, held until it starts
+    let _res = app
+        .execute_contract(
+            sponsor.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Fund{ game_id: 1, round: Some(2) },
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(3000000000000000000_u128)
+            }]
+        )
+        .unwrap();
+    let status: StatusResponse = query(&mut app, netwars_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(status.pot, Uint128::from(8000000000000000000_u128));
+    let res: SponsorsResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Sponsors{ game_id: 1, round: Some(2), start_after: None, limit: None },
+    ).unwrap();
+    assert_eq!(res.total, Uint128::from(3000000000000000000_u128));
+    assert_eq!(res.sponsors[0].address, sponsor);
+    // held funds aren't surplus
+    let surplus: SurplusResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Surplus{ asset: AssetInfo::Native { denom: DENOM.to_string() } },
+    ).unwrap();
+    assert_eq!(surplus.surplus, Uint128::zero());
+
+    // rounds too far ahead can't be funded
+    let err = app
+        .execute_contract(
+            sponsor.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Fund{ game_id: 1, round: Some(MAX_FUND_AHEAD + 2) },
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        ContractError::ValueTooLarge { field: "round".to_string(), max: MAX_FUND_AHEAD + 1 }.to_string()
+    );
+
+    // sponsorships can be withdrawn until their round starts
+    let _res = app
+        .execute_contract(
+            sponsor.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Fund{ game_id: 1, round: Some(MAX_FUND_AHEAD + 1) },
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        )
+        .unwrap();
+    let _res = app
+        .execute_contract(
+            sponsor.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::WithdrawSponsorship{ game_id: 1, round: MAX_FUND_AHEAD + 1 },
+            &[]
+        )
+        .unwrap();
+    let sponsor_balance: Coin = bank_query(&mut app, &sponsor);
+    assert_eq!(sponsor_balance.amount, Uint128::from(5000000000000000000_u128));
+    let res: SponsorsResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Sponsors{ game_id: 1, round: Some(MAX_FUND_AHEAD + 1), start_after: None, limit: None },
+    ).unwrap();
+    assert_eq!(res.total, Uint128::zero());
+    assert!(res.sponsors.is_empty());
+    let err = app
+        .execute_contract(
+            sponsor.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::WithdrawSponsorship{ game_id: 1, round: MAX_FUND_AHEAD + 1 },
+            &[]
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), ContractError::NoSponsorship {}.to_string());
+
+    // but not once it's under way
+    let err = app
+        .execute_contract(
+            sponsor.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::WithdrawSponsorship{ game_id: 1, round: 1 },
+            &[]
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), ContractError::RoundStarted { round: 1 }.to_string());
+
+    // expired rounds can't be funded
+    let current_time = get_block_time(&mut app);
+    increment_block_time(&mut app, current_time + 1000, 7);
+    let err = app
+        .execute_contract(
+            sponsor.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Fund{ game_id: 1, round: None },
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), ContractError::Gameover {}.to_string());
+
+    // winner takes the current pot, the next round
+    // starts with its sponsored funds
+    let res = app
+        .execute_contract(
+            depositor.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Claim{ game_id: 1 },
+            &[]
+        )
+        .unwrap();
+    let sponsored = res.events
+        .iter()
+        .flat_map(|e| e.attributes.iter())
+        .find(|a| a.key == "sponsored")
+        .unwrap();
+    assert_eq!(sponsored.value, "3000000000000000000");
+    let winner_balance: Coin = bank_query(&mut app, &depositor);
+    assert_eq!(winner_balance.amount, Uint128::from(17000000000000000000_u128));
+    let status: StatusResponse = query(&mut app, netwars_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(status.round, 2);
+    assert_eq!(status.pot, Uint128::from(3000000000000000000_u128));
+
+    // past rounds can't be funded
+    let err = app
+        .execute_contract(
+            sponsor.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Fund{ game_id: 1, round: Some(1) },
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), ContractError::RoundEnded { round: 1 }.to_string());
+}
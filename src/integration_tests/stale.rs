@@ -12,8 +12,8 @@ use crate::integration_tests::util::{
 use crate::msg::{
     ExecuteMsg, QueryMsg,
 };
-use crate::contract::DENOM;
-use crate::state::{State};
+use crate::integration_tests::util::DENOM;
+use crate::state::{RoundState};
 
 // When game is won winner must claim their prize
 // within the allotted period; otherwise, anyone can 
@@ -83,7 +83,7 @@ fn test_unlock_stale() {
         .execute_contract(
             first_depositor.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Deposit{}, 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
             &[Coin {
                 denom: String::from(DENOM),
                 amount: Uint128::from(1000000000000000000_u128)
@@ -96,7 +96,7 @@ fn test_unlock_stale() {
         .execute_contract(
             second_depositor.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Deposit{}, 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
             &[Coin {
                 denom: String::from(DENOM),
                 amount: Uint128::from(1000000000000000000_u128)
@@ -114,7 +114,7 @@ fn test_unlock_stale() {
         app.execute_contract(
             first_depositor.clone(), 
             netwars_addr.clone(),
-            &ExecuteMsg::Deposit{}, 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
             &[Coin {
                 denom: String::from(DENOM),
                 amount: Uint128::from(1000000000000000000_u128)
@@ -127,7 +127,7 @@ fn test_unlock_stale() {
         app.execute_contract(
             first_depositor.clone(),
             netwars_addr.clone(),
-            &ExecuteMsg::UnlockStale{},
+            &ExecuteMsg::UnlockStale{ game_id: 1 },
             &[]
         ).is_err()
     );
@@ -140,7 +140,7 @@ fn test_unlock_stale() {
     let res = app.execute_contract(
         complete_random.clone(),
         netwars_addr.clone(),
-        &ExecuteMsg::UnlockStale{},
+        &ExecuteMsg::UnlockStale{ game_id: 1 },
         &[]
     );
     assert!(res.is_ok());
@@ -152,10 +152,10 @@ fn test_unlock_stale() {
 
     // game was correctly restarted, and
     // round was increased
-    let game_query: State = query(
+    let game_query: RoundState = query(
         &mut app,
         netwars_addr.clone(),
-        QueryMsg::Game{},
+        QueryMsg::CurrentRound{ game_id: 1 },
     ).unwrap();
     assert_eq!(game_query.round, 2_u64);
 
@@ -165,7 +165,7 @@ fn test_unlock_stale() {
         .execute_contract(
             first_depositor.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Deposit{}, 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
             &[Coin {
                 denom: String::from(DENOM),
                 amount: Uint128::from(1000000000000000000_u128)
@@ -178,7 +178,7 @@ fn test_unlock_stale() {
         .execute_contract(
             complete_random.clone(),
             netwars_addr.clone(), 
-            &ExecuteMsg::Deposit{}, 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
             &[Coin {
                 denom: String::from(DENOM),
                 amount: Uint128::from(1000000000000000000_u128)
@@ -191,7 +191,7 @@ fn test_unlock_stale() {
         .execute_contract(
             first_depositor.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Deposit{}, 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
             &[Coin {
                 denom: String::from(DENOM),
                 amount: Uint128::from(1000000000000000000_u128)
@@ -213,7 +213,7 @@ fn test_unlock_stale() {
         .execute_contract(
             first_depositor.clone(), 
             netwars_addr.clone(), 
-            &ExecuteMsg::Claim{}, 
+            &ExecuteMsg::Claim{ game_id: 1 }, 
             &[]
         )
         .unwrap();
@@ -232,10 +232,10 @@ fn test_unlock_stale() {
 
     // game was correctly restarted, and
     // round was increased
-    let game_query: State = query(
+    let game_query: RoundState = query(
         &mut app,
         netwars_addr.clone(),
-        QueryMsg::Game{},
+        QueryMsg::CurrentRound{ game_id: 1 },
     ).unwrap();
     assert_eq!(game_query.round, 3_u64);
 }
\ No newline at end of file
@@ -0,0 +1,107 @@
+#![cfg(test)]
+use cosmwasm_std::{
+    Addr, Coin, Uint128,
+};
+use cw_multi_test::Executor;
+
+use crate::integration_tests::util::{
+    create_netwars, get_block_time, increment_block_time, mint_native, mock_app, query, DENOM,
+};
+
+use crate::msg::{
+    ExecuteMsg, QueryMsg, StatusResponse,
+};
+use crate::state::GamePhase;
+
+// Status query reports the game phase, timers, pot, 
+// price and leader as of the current block
+#[test]
+fn test_status() {
+    let mut app = mock_app();
+    
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // depositor owns ARCH
+    let depositor = Addr::unchecked("arch_owner");
+
+    // mint arch to depositor
+    mint_native(
+        &mut app,
+        depositor.to_string(),
+        Uint128::from(10000000000000000000_u128), // 10 ARCH as aarch
+    );
+
+    let netwars_addr: Addr = create_netwars(
+        &mut app, 
+        &netwars_admin, 
+        None,
+        None,
+        120, // 2 minutes
+        Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+        30, // 30 seconds
+        600, // 10 minutes
+        604800, // ~1 week
+        &[],
+    );
+    let start_time = get_block_time(&mut app);
+
+    // depositor makes a deposit
+    let _res = app
+        .execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        )
+        .unwrap();
+
+    // round is active
+    increment_block_time(&mut app, start_time + 50, 7);
+    let res: StatusResponse = query(&mut app, netwars_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(res, StatusResponse {
+        round: 1,
+        phase: GamePhase::Active,
+        expiration: start_time + 150,
+        seconds_remaining: 100,
+        stale_at: start_time + 750,
+        pot: Uint128::from(1000000000000000000_u128),
+        carry_over: Uint128::zero(),
+        price: Some(Uint128::from(1000000000000000000_u128)),
+        leader: Some(depositor.clone()),
+        leader_name: None,
+    });
+
+    // paused games report paused
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Pause{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+    let res: StatusResponse = query(&mut app, netwars_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(res.phase, GamePhase::Paused);
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Unpause{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+
+    // timer ran out, leader can claim
+    increment_block_time(&mut app, start_time + 150, 7);
+    let res: StatusResponse = query(&mut app, netwars_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(res.phase, GamePhase::AwaitingClaim);
+    assert_eq!(res.seconds_remaining, 0);
+
+    // unclaimed prize becomes stale
+    increment_block_time(&mut app, start_time + 750, 7);
+    let res: StatusResponse = query(&mut app, netwars_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(res.phase, GamePhase::Stale);
+}
@@ -0,0 +1,140 @@
+#![cfg(test)]
+use cosmwasm_std::{
+    Addr, Coin, Uint128,
+};
+use cw_multi_test::Executor;
+
+use crate::integration_tests::util::{
+    bank_query, increment_block_time, get_block_time, instantiate_msg, instantiate_netwars,
+    mint_native, mock_app, query, DENOM,
+};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, QueryMsg, StatusResponse, SurplusResponse,
+};
+use crate::state::{AssetInfo, PayoutSplit};
+
+// Prizes are paid from the tracked pot, funds the games
+// don't owe can only be swept by the admin
+#[test]
+fn test_sweep() {
+    let mut app = mock_app();
+
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // depositor owns ARCH
+    let depositor = Addr::unchecked("arch_owner");
+    // receives swept funds
+    let treasury = Addr::unchecked("treasury");
+
+    mint_native(
+        &mut app,
+        netwars_admin.to_string(),
+        Uint128::from(5000000000000000000_u128), // 5 ARCH as aarch
+    );
+    mint_native(
+        &mut app,
+        depositor.to_string(),
+        Uint128::from(10000000000000000000_u128), // 10 ARCH as aarch
+    );
+
+    // 10% fee, winner takes 80% and 20% carries over
+    let msg = InstantiateMsg {
+        payout: Some(PayoutSplit {
+            winner_bps: 8000,
+            dividend_bps: 0,
+            carry_over_bps: 2000,
+        }),
+        fee_bps: Some(1000),
+        ..instantiate_msg()
+    };
+    let netwars_addr: Addr = instantiate_netwars(
+        &mut app,
+        &netwars_admin,
+        &msg,
+        &[Coin {
+            denom: String::from(DENOM),
+            amount: Uint128::from(5000000000000000000_u128)
+        }],
+    );
+
+    // seed funds start the pot
+    let status: StatusResponse = query(&mut app, netwars_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(status.pot, Uint128::from(5000000000000000000_u128));
+    assert_eq!(status.carry_over, Uint128::zero());
+
+    // a stray transfer isn't added to the pot
+    mint_native(
+        &mut app,
+        netwars_addr.to_string(),
+        Uint128::from(3000000000000000000_u128), // 3 ARCH as aarch
+    );
+    let _res = app
+        .execute_contract(
+            depositor.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None },
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        )
+        .unwrap();
+    let status: StatusResponse = query(&mut app, netwars_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(status.pot, Uint128::from(5900000000000000000_u128));
+    let surplus: SurplusResponse = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::Surplus{ asset: AssetInfo::Native { denom: DENOM.to_string() } },
+    ).unwrap();
+    assert_eq!(surplus.balance, Uint128::from(9000000000000000000_u128));
+    // pot and accrued fees
+    assert_eq!(surplus.accounted, Uint128::from(6000000000000000000_u128));
+    assert_eq!(surplus.surplus, Uint128::from(3000000000000000000_u128));
+
+    // only fee managers can sweep
+    let sweep_msg = ExecuteMsg::Sweep {
+        asset: AssetInfo::Native { denom: DENOM.to_string() },
+        recipient: Some(treasury.to_string()),
+    };
+    let err = app
+        .execute_contract(depositor.clone(), netwars_addr.clone(), &sweep_msg, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), ContractError::Unauthorized {}.to_string());
+
+    // only the surplus is swept
+    let _res = app
+        .execute_contract(netwars_admin.clone(), netwars_addr.clone(), &sweep_msg, &[])
+        .unwrap();
+    let treasury_balance: Coin = bank_query(&mut app, &treasury);
+    assert_eq!(treasury_balance.amount, Uint128::from(3000000000000000000_u128));
+    let err = app
+        .execute_contract(netwars_admin.clone(), netwars_addr.clone(), &sweep_msg, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), ContractError::NoSurplus {}.to_string());
+
+    // winner is paid from the pot, leaving fees and carry over
+    let current_time = get_block_time(&mut app);
+    increment_block_time(&mut app, current_time + 1000, 7);
+    let res = app
+        .execute_contract(
+            depositor.clone(),
+            netwars_addr.clone(),
+            &ExecuteMsg::Claim{ game_id: 1 },
+            &[]
+        )
+        .unwrap();
+    let prize = res.events
+        .iter()
+        .flat_map(|e| e.attributes.iter())
+        .find(|a| a.key == "prize")
+        .unwrap();
+    assert_eq!(prize.value, "4720000000000000000");
+    let status: StatusResponse = query(&mut app, netwars_addr.clone(), QueryMsg::Status{ game_id: 1 }).unwrap();
+    assert_eq!(status.round, 2);
+    assert_eq!(status.pot, Uint128::from(1180000000000000000_u128));
+    assert_eq!(status.carry_over, Uint128::from(1180000000000000000_u128));
+    let netwars_balance: Coin = bank_query(&mut app, &netwars_addr);
+    assert_eq!(netwars_balance.amount, Uint128::from(1280000000000000000_u128));
+}
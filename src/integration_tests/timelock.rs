@@ -0,0 +1,204 @@
+#![cfg(test)]
+use cosmwasm_std::{
+    Addr, Coin, Uint128,
+};
+use cw_multi_test::Executor;
+
+use crate::integration_tests::util::{
+    get_block_time, increment_block_time, instantiate_msg, instantiate_netwars, mint_native, mock_app, query, DENOM,
+};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigureMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+use crate::state::{Config, PendingConfig, RoundState};
+
+// With a config delay, configuration changes are queued
+// and only apply at the next round or after the delay
+#[test]
+fn test_timelocked_config() {
+    let mut app = mock_app();
+    
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // depositor owns ARCH
+    let depositor = Addr::unchecked("arch_owner");
+
+    // mint arch to depositor
+    mint_native(
+        &mut app,
+        depositor.to_string(),
+        Uint128::from(10000000000000000000_u128), // 10 ARCH as aarch
+    );
+
+    let msg = InstantiateMsg {
+        config_delay: Some(3600), // 1 hour
+        ..instantiate_msg()
+    };
+    let netwars_addr: Addr = instantiate_netwars(&mut app, &netwars_admin, &msg, &[]);
+
+    let mut config_msg = ConfigureMsg {
+        min_deposit: Some(Uint128::from(2000000000000000000_u128)), // 2 ARCH as aarch
+        ..Default::default()
+    };
+
+    // admin queues a min_deposit change
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Configure { game_id: 1, msg: config_msg.clone() }, 
+            &[]
+        )
+        .unwrap();
+    let current_time = get_block_time(&mut app);
+    let res: Option<PendingConfig> = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::PendingConfig{ game_id: 1 },
+    ).unwrap();
+    assert_eq!(res, Some(PendingConfig {
+        config: config_msg.clone(),
+        effective_at: current_time + 3600,
+        effective_round: 2,
+    }));
+
+    // change is not applied yet
+    let config_query: Config = query(&mut app, netwars_addr.clone(), QueryMsg::Config{ game_id: 1 }).unwrap();
+    assert_eq!(config_query.min_deposit, Uint128::from(1000000000000000000_u128));
+    assert!(
+        app.execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::ApplyConfig{ game_id: 1 }, 
+            &[]
+        ).is_err()
+    );
+
+    // only configurators can cancel
+    assert!(
+        app.execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::CancelConfig{ game_id: 1 }, 
+            &[]
+        ).is_err()
+    );
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::CancelConfig{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+    let res: Option<PendingConfig> = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::PendingConfig{ game_id: 1 },
+    ).unwrap();
+    assert_eq!(res, None);
+
+    // invalid changes are rejected when queued
+    let mut invalid_config_msg = config_msg.clone();
+    invalid_config_msg.fee_bps = Some(10001);
+    assert!(
+        app.execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Configure { game_id: 1, msg: invalid_config_msg }, 
+            &[]
+        ).is_err()
+    );
+
+    // admin queues the change again
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Configure { game_id: 1, msg: config_msg.clone() }, 
+            &[]
+        )
+        .unwrap();
+
+    // queued changes can't be overwritten
+    let mut other_config_msg = config_msg.clone();
+    other_config_msg.min_deposit = Some(Uint128::from(3000000000000000000_u128)); // 3 ARCH as aarch
+    let err = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Configure { game_id: 1, msg: other_config_msg }, 
+            &[]
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), ContractError::ConfigPending {}.to_string());
+    let res: Option<PendingConfig> = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::PendingConfig{ game_id: 1 },
+    ).unwrap();
+    assert_eq!(res.unwrap().config, config_msg);
+
+    // current round is played with the old settings
+    let _res = app
+        .execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        )
+        .unwrap();
+
+    // change applies when the next round starts
+    increment_block_time(&mut app, current_time + 1000, 7);
+    let _res = app
+        .execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Claim{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+    let game_query: RoundState = query(&mut app, netwars_addr.clone(), QueryMsg::CurrentRound{ game_id: 1 }).unwrap();
+    assert_eq!(game_query.round, 2_u64);
+    let config_query: Config = query(&mut app, netwars_addr.clone(), QueryMsg::Config{ game_id: 1 }).unwrap();
+    assert_eq!(config_query.min_deposit, Uint128::from(2000000000000000000_u128));
+    let res: Option<PendingConfig> = query(
+        &mut app,
+        netwars_addr.clone(),
+        QueryMsg::PendingConfig{ game_id: 1 },
+    ).unwrap();
+    assert_eq!(res, None);
+
+    // admin queues an extensions change
+    config_msg.min_deposit = None;
+    config_msg.extensions = Some(60);
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Configure { game_id: 1, msg: config_msg }, 
+            &[]
+        )
+        .unwrap();
+
+    // anyone can apply it once the delay has passed
+    increment_block_time(&mut app, current_time + 1000 + 3600, 7);
+    let _res = app
+        .execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::ApplyConfig{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+    let game_query: RoundState = query(&mut app, netwars_addr.clone(), QueryMsg::CurrentRound{ game_id: 1 }).unwrap();
+    assert_eq!(game_query.round, 2_u64);
+    let config_query: Config = query(&mut app, netwars_addr.clone(), QueryMsg::Config{ game_id: 1 }).unwrap();
+    assert_eq!(config_query.extensions, 60_u64);
+}
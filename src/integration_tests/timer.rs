@@ -0,0 +1,129 @@
+#![cfg(test)]
+use cosmwasm_std::{
+    Addr, Coin, Uint128,
+};
+use cw_multi_test::Executor;
+
+use crate::integration_tests::util::{
+    increment_block_time, get_block_time, instantiate_msg, instantiate_netwars, mint_native, mock_app, query, 
+    DENOM,
+};
+
+use crate::msg::{
+    ConfigureMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+use crate::state::{Config, RoundState};
+
+// Deposits can't push the timer further than max_remaining
+// from now, and no deposits are accepted once a round has
+// run for max_round_duration
+#[test]
+fn test_timer_caps() {
+    let mut app = mock_app();
+    
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+    // depositor owns ARCH
+    let depositor = Addr::unchecked("arch_owner");
+
+    // mint arch to depositor
+    mint_native(
+        &mut app,
+        depositor.to_string(),
+        Uint128::from(100000000000000000000_u128), // 100 ARCH as aarch
+    );
+
+    let msg = InstantiateMsg {
+        extensions: 3600, // 1 hour
+        max_remaining: Some(600), // 10 minutes
+        max_round_duration: Some(1000),
+        ..instantiate_msg()
+    };
+    let netwars_addr: Addr = instantiate_netwars(&mut app, &netwars_admin, &msg, &[]);
+    let round_start = get_block_time(&mut app);
+
+    let deposit = |app: &mut cw_multi_test::App| {
+        app.execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Deposit{ game_id: 1, quantity: None, referrer: None }, 
+            &[Coin {
+                denom: String::from(DENOM),
+                amount: Uint128::from(1000000000000000000_u128)
+            }]
+        )
+    };
+
+    // extension is capped at max_remaining from now
+    deposit(&mut app).unwrap();
+    let game_query: RoundState = query(&mut app, netwars_addr.clone(), QueryMsg::CurrentRound{ game_id: 1 }).unwrap();
+    assert_eq!(game_query.expiration, round_start + 600);
+
+    increment_block_time(&mut app, round_start + 300, 7);
+    deposit(&mut app).unwrap();
+    let game_query: RoundState = query(&mut app, netwars_addr.clone(), QueryMsg::CurrentRound{ game_id: 1 }).unwrap();
+    assert_eq!(game_query.expiration, round_start + 900);
+
+    // round is still playable before the hard cap
+    increment_block_time(&mut app, round_start + 850, 7);
+    deposit(&mut app).unwrap();
+    let game_query: RoundState = query(&mut app, netwars_addr.clone(), QueryMsg::CurrentRound{ game_id: 1 }).unwrap();
+    assert_eq!(game_query.expiration, round_start + 1450);
+
+    // deposits are rejected once max_round_duration is reached
+    increment_block_time(&mut app, round_start + 1000, 7);
+    assert!(deposit(&mut app).is_err());
+
+    // round is still running so it can't be claimed yet
+    assert!(app
+        .execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Claim{ game_id: 1 }, 
+            &[]
+        ).is_err()
+    );
+
+    // winner claims when the timer runs out
+    increment_block_time(&mut app, round_start + 1450, 7);
+    let _res = app
+        .execute_contract(
+            depositor.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Claim{ game_id: 1 }, 
+            &[]
+        )
+        .unwrap();
+
+    // hard cap is measured from the start of the new round
+    let game_query: RoundState = query(&mut app, netwars_addr.clone(), QueryMsg::CurrentRound{ game_id: 1 }).unwrap();
+    assert_eq!(game_query.round, 2_u64);
+    assert_eq!(game_query.round_start, round_start + 1450);
+    deposit(&mut app).unwrap();
+
+    // admin disables both caps
+    let config_msg = ConfigureMsg {
+        max_remaining: Some(0),
+        max_round_duration: Some(0),
+        ..Default::default()
+    };
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Configure { game_id: 1, msg: config_msg }, 
+            &[]
+        )
+        .unwrap();
+    let config_query: Config = query(&mut app, netwars_addr.clone(), QueryMsg::Config{ game_id: 1 }).unwrap();
+    assert_eq!(config_query.max_remaining, None);
+    assert_eq!(config_query.max_round_duration, None);
+    let game_query: RoundState = query(&mut app, netwars_addr.clone(), QueryMsg::CurrentRound{ game_id: 1 }).unwrap();
+
+    // deposits extend the timer by the full extension
+    let current_time = get_block_time(&mut app);
+    increment_block_time(&mut app, current_time + 100, 7);
+    deposit(&mut app).unwrap();
+    let new_game_query: RoundState = query(&mut app, netwars_addr.clone(), QueryMsg::CurrentRound{ game_id: 1 }).unwrap();
+    assert_eq!(new_game_query.expiration, game_query.expiration + 3600);
+}
@@ -15,9 +15,13 @@ use archid_registry::{
 use archid_token::{
     InstantiateMsg as Cw721InstantiateMsg,
 };
+use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20Coin, Cw20QueryMsg};
+use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
 
 use crate::msg::InstantiateMsg;
-use crate::contract::DENOM;
+use crate::state::{AssetInfo, EligibilityRule};
+
+pub static DENOM: &str = "aarch";
 
 pub fn mock_app() -> App {
     App::default()
@@ -39,7 +43,8 @@ pub fn contract_netwars() -> Box<dyn Contract<Empty>> {
         crate::contract::execute,
         crate::contract::instantiate,
         crate::contract::query,
-    );
+    )
+    .with_migrate(crate::contract::migrate);
     Box::new(contract)
 }
 
@@ -61,6 +66,40 @@ pub fn contract_cw721() -> Box<dyn Contract<Empty>> {
     Box::new(contract)
 }
 
+pub fn contract_cw20() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
+    Box::new(contract)
+}
+
+// Game played with ARCH, optional settings left unset
+pub fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        asset: AssetInfo::Native { denom: DENOM.to_string() },
+        archid_registry: None,
+        expiration: 120, // 2 minutes
+        min_deposit: Uint128::from(1000000000000000000_u128), // 1 ARCH as aarch
+        extensions: 30, // 30 seconds
+        stale: 604800, // ~1 week
+        reset_length: 604800, // ~1 week
+        payout: None,
+        fee_bps: None,
+        fee_recipient: None,
+        price_curve: None,
+        max_remaining: None,
+        max_round_duration: None,
+        funds_policy: None,
+        config_delay: None,
+        referral_bps: None,
+        unlock_reward_bps: None,
+        keeper_bounty: None,
+        eligibility: None,
+    }
+}
+
 pub fn create_netwars(
     router: &mut App, 
     owner: &Addr,
@@ -73,18 +112,31 @@ pub fn create_netwars(
     reset_length: u64,
     funds: &[Coin],
 ) -> Addr {
-    let netwars_id = router.store_code(contract_netwars());
     let msg = InstantiateMsg {
-        archid_registry,
-        archid_cw721,
+        archid_registry: archid_registry.as_ref().map(|addr| addr.to_string()),
         expiration,
         min_deposit,
         extensions,
         stale,
         reset_length,
+        // Players must own an ArchID when both contracts are given
+        eligibility: archid_registry
+            .zip(archid_cw721)
+            .map(|(_, cw721)| EligibilityRule::ArchId { cw721 }),
+        ..instantiate_msg()
     };
+    instantiate_netwars(router, owner, &msg, funds)
+}
+
+pub fn instantiate_netwars(
+    router: &mut App, 
+    owner: &Addr,
+    msg: &InstantiateMsg,
+    funds: &[Coin],
+) -> Addr {
+    let netwars_id = router.store_code(contract_netwars());
     let netwars_addr = router
-        .instantiate_contract(netwars_id, owner.clone(), &msg, funds, "Netwars", None)
+        .instantiate_contract(netwars_id, owner.clone(), msg, funds, "Netwars", None)
         .unwrap();
     
     netwars_addr
@@ -125,6 +177,22 @@ pub fn create_cw721(router: &mut App, minter: &Addr) -> Addr {
     contract
 }
 
+pub fn create_cw20(router: &mut App, owner: &Addr, initial_balances: Vec<Cw20Coin>) -> Addr {
+    let cw20_id = router.store_code(contract_cw20());
+    let msg = Cw20InstantiateMsg {
+        name: "Network Token".to_string(),
+        symbol: "NET".to_string(),
+        decimals: 18,
+        initial_balances,
+        mint: None,
+        marketing: None,
+    };
+    let contract = router
+        .instantiate_contract(cw20_id, owner.clone(), &msg, &[], "cw20", None)
+        .unwrap();
+    contract
+}
+
 pub fn mint_native(app: &mut App, beneficiary: String, amount: Uint128) {
     app.sudo(cw_multi_test::SudoMsg::Bank(
         cw_multi_test::BankSudo::Mint {
@@ -157,4 +225,12 @@ pub fn bank_query(app: &App, address: &Addr) -> Coin {
     let res = app.raw_query(&to_binary(&req).unwrap()).unwrap().unwrap();
     let balance: BalanceResponseBank = from_binary(&res).unwrap();
     return balance.amount;
-}
\ No newline at end of file
+}
+
+pub fn cw20_balance_query(app: &App, token: &Addr, address: &Addr) -> Uint128 {
+    let res: Cw20BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(token, &Cw20QueryMsg::Balance { address: address.to_string() })
+        .unwrap();
+    res.balance
+}
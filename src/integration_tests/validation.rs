@@ -0,0 +1,180 @@
+#![cfg(test)]
+use cosmwasm_std::{
+    Addr, Uint128,
+};
+use cw_multi_test::{App, Executor};
+
+use crate::integration_tests::util::{
+    contract_netwars, get_block_time, instantiate_msg, instantiate_netwars, mock_app, query,
+};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigureMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+use crate::state::{MAX_DURATION, MAX_GROWTH_BPS, PriceCurve, PriceStep, RoundState};
+
+fn instantiate_err(app: &mut App, owner: &Addr, msg: &InstantiateMsg) -> String {
+    let code_id = app.store_code(contract_netwars());
+    app.instantiate_contract(code_id, owner.clone(), msg, &[], "Netwars", None)
+        .unwrap_err()
+        .root_cause()
+        .to_string()
+}
+
+fn configure_err(app: &mut App, owner: &Addr, contract: &Addr, msg: ConfigureMsg) -> String {
+    app.execute_contract(owner.clone(), contract.clone(), &ExecuteMsg::Configure { game_id: 1, msg }, &[])
+        .unwrap_err()
+        .root_cause()
+        .to_string()
+}
+
+fn zero_value(field: &str) -> String {
+    ContractError::ZeroValue { field: field.to_string() }.to_string()
+}
+
+fn too_large(field: &str) -> String {
+    ContractError::ValueTooLarge { field: field.to_string(), max: MAX_DURATION }.to_string()
+}
+
+fn too_small(field: &str, min: Uint128) -> String {
+    ContractError::ValueTooSmall { field: field.to_string(), min }.to_string()
+}
+
+fn steps(prices: &[u128]) -> Option<PriceCurve> {
+    let steps = prices
+        .iter()
+        .enumerate()
+        .map(|(index, price)| PriceStep { deposits: index as u64 + 1, price: Uint128::from(*price) })
+        .collect();
+    Some(PriceCurve::Steps { steps })
+}
+
+// Game parameters out of bounds are rejected with an error
+// naming the bad field, and expiration is always relative
+#[test]
+fn test_config_validation() {
+    let mut app = mock_app();
+    
+    // netwars owner deploys netwars
+    let netwars_admin = Addr::unchecked("netwars_deployer");
+
+    let msg = instantiate_msg();
+
+    // instantiate rejects each invalid parameter
+    let invalid_msgs = vec![
+        (InstantiateMsg { expiration: 0, ..msg.clone() }, zero_value("expiration")),
+        (InstantiateMsg { expiration: MAX_DURATION + 1, ..msg.clone() }, too_large("expiration")),
+        (InstantiateMsg { min_deposit: Uint128::zero(), ..msg.clone() }, zero_value("min_deposit")),
+        (InstantiateMsg { extensions: 0, ..msg.clone() }, zero_value("extensions")),
+        (InstantiateMsg { extensions: MAX_DURATION + 1, ..msg.clone() }, too_large("extensions")),
+        (InstantiateMsg { stale: 0, ..msg.clone() }, zero_value("stale")),
+        (InstantiateMsg { stale: MAX_DURATION + 1, ..msg.clone() }, too_large("stale")),
+        (InstantiateMsg { reset_length: 0, ..msg.clone() }, zero_value("reset_length")),
+        (InstantiateMsg { reset_length: MAX_DURATION + 1, ..msg.clone() }, too_large("reset_length")),
+        (InstantiateMsg { max_remaining: Some(MAX_DURATION + 1), ..msg.clone() }, too_large("max_remaining")),
+        (
+            InstantiateMsg { max_round_duration: Some(MAX_DURATION + 1), ..msg.clone() },
+            too_large("max_round_duration"),
+        ),
+        (InstantiateMsg { config_delay: Some(MAX_DURATION + 1), ..msg.clone() }, too_large("config_delay")),
+        (
+            InstantiateMsg { price_curve: steps(&[2000000000000000000, 0]), ..msg.clone() },
+            zero_value("steps[1].price"),
+        ),
+        (
+            InstantiateMsg { price_curve: steps(&[500000000000000000]), ..msg.clone() },
+            too_small("steps[0].price", msg.min_deposit),
+        ),
+        (
+            InstantiateMsg {
+                price_curve: Some(PriceCurve::Exponential { growth_bps: MAX_GROWTH_BPS + 1 }),
+                ..msg.clone()
+            },
+            ContractError::ValueTooLarge { field: "growth_bps".to_string(), max: MAX_GROWTH_BPS }.to_string(),
+        ),
+    ];
+    for (invalid_msg, expected_err) in invalid_msgs {
+        assert_eq!(instantiate_err(&mut app, &netwars_admin, &invalid_msg), expected_err);
+    }
+
+    // valid parameters are accepted
+    let netwars_addr: Addr = instantiate_netwars(&mut app, &netwars_admin, &msg, &[]);
+
+    // configure rejects each invalid parameter
+    let invalid_msgs = vec![
+        (ConfigureMsg { expiration: Some(0), ..Default::default() }, zero_value("expiration")),
+        (ConfigureMsg { expiration: Some(MAX_DURATION + 1), ..Default::default() }, too_large("expiration")),
+        (ConfigureMsg { min_deposit: Some(Uint128::zero()), ..Default::default() }, zero_value("min_deposit")),
+        (ConfigureMsg { extensions: Some(0), ..Default::default() }, zero_value("extensions")),
+        (ConfigureMsg { extensions: Some(MAX_DURATION + 1), ..Default::default() }, too_large("extensions")),
+        (ConfigureMsg { stale: Some(0), ..Default::default() }, zero_value("stale")),
+        (ConfigureMsg { stale: Some(MAX_DURATION + 1), ..Default::default() }, too_large("stale")),
+        (ConfigureMsg { reset_length: Some(0), ..Default::default() }, zero_value("reset_length")),
+        (ConfigureMsg { reset_length: Some(MAX_DURATION + 1), ..Default::default() }, too_large("reset_length")),
+        (ConfigureMsg { max_remaining: Some(MAX_DURATION + 1), ..Default::default() }, too_large("max_remaining")),
+        (
+            ConfigureMsg { max_round_duration: Some(MAX_DURATION + 1), ..Default::default() },
+            too_large("max_round_duration"),
+        ),
+        (ConfigureMsg { config_delay: Some(MAX_DURATION + 1), ..Default::default() }, too_large("config_delay")),
+        (
+            ConfigureMsg { price_curve: steps(&[0]), ..Default::default() },
+            zero_value("steps[0].price"),
+        ),
+        (
+            ConfigureMsg {
+                min_deposit: Some(Uint128::from(3000000000000000000_u128)),
+                price_curve: steps(&[2000000000000000000]),
+                ..Default::default()
+            },
+            too_small("steps[0].price", Uint128::from(3000000000000000000_u128)),
+        ),
+    ];
+    for (invalid_msg, expected_err) in invalid_msgs {
+        assert_eq!(
+            configure_err(&mut app, &netwars_admin, &netwars_addr, invalid_msg), 
+            expected_err,
+        );
+    }
+
+    // ownership proposals can't outlive MAX_DURATION
+    let err = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::ProposeOwner { new_owner: String::from("new_admin"), expiry: Some(MAX_DURATION + 1) }, 
+            &[]
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), too_large("expiry"));
+
+    // limits of 0 are disabled rather than rejected
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Configure { 
+                game_id: 1, 
+                msg: ConfigureMsg { max_remaining: Some(0), max_round_duration: Some(0), ..Default::default() },
+            }, 
+            &[]
+        )
+        .unwrap();
+
+    // expiration is relative to the current block time
+    let _res = app
+        .execute_contract(
+            netwars_admin.clone(), 
+            netwars_addr.clone(), 
+            &ExecuteMsg::Configure { 
+                game_id: 1, 
+                msg: ConfigureMsg { expiration: Some(3600), ..Default::default() },
+            }, 
+            &[]
+        )
+        .unwrap();
+    let current_time = get_block_time(&mut app);
+    let game_query: RoundState = query(&mut app, netwars_addr, QueryMsg::CurrentRound{ game_id: 1 }).unwrap();
+    assert_eq!(game_query.expiration, current_time + 3600);
+}
@@ -3,6 +3,7 @@ mod integration_tests;
 
 pub mod contract;
 pub mod execute;
+pub mod migrate;
 pub mod query;
 pub mod msg;
 pub mod state;
@@ -0,0 +1,141 @@
+use cosmwasm_std::{DepsMut, Env, Order, StdResult, Storage, Uint128};
+use semver::Version;
+
+use crate::msg::MigrateMsg;
+use crate::state::{
+    Archid, AssetInfo, Config, CONFIGS, CURRENT_ROUNDS, EligibilityRule, GAME_COUNT, LEGACY_ARCHID, LEGACY_STATE,
+    OWNER, POTS, RoundState,
+};
+use crate::error::ContractError;
+
+type MigrationStep = fn(DepsMut, &Env, &MigrateMsg) -> Result<(), ContractError>;
+
+// Ordered migration steps, each one runs when upgrading
+// from a version older than its own
+const MIGRATIONS: &[(&str, MigrationStep)] = &[
+    ("0.3.1", migrate_multi_game),
+];
+
+// Game created from the single game layout
+const MIGRATED_GAME_ID: u64 = 1;
+
+// Run every step newer than `from`, in order, returning
+// the versions that were applied
+pub fn run_migrations(
+    mut deps: DepsMut,
+    env: &Env,
+    from: &Version,
+    msg: &MigrateMsg,
+) -> Result<Vec<String>, ContractError> {
+    let mut applied: Vec<String> = vec![];
+    for (version, step) in MIGRATIONS {
+        if *from < Version::parse(version)? {
+            step(deps.branch(), env, msg)?;
+            applied.push(version.to_string());
+        }
+    }
+    Ok(applied)
+}
+
+// Game play is paused (in every game), in either storage layout
+pub fn is_paused(storage: &dyn Storage) -> StdResult<bool> {
+    if GAME_COUNT.may_load(storage)?.is_some() {
+        for item in CURRENT_ROUNDS.range(storage, None, None, Order::Ascending) {
+            let (_, state) = item?;
+            if !state.is_paused() {
+                return Ok(false);
+            }
+        }
+        return Ok(true);
+    }
+    let legacy = LEGACY_STATE.load(storage)?;
+    Ok(legacy.paused.is_some())
+}
+
+// 0.3.1: Move the single state item (and ArchID item) of 0.1.4
+// and earlier into the multi game layout, as game 1, with the
+// contract balance as its pot
+fn migrate_multi_game(
+    deps: DepsMut,
+    env: &Env,
+    msg: &MigrateMsg,
+) -> Result<(), ContractError> {
+    let game_id = MIGRATED_GAME_ID;
+    let legacy = LEGACY_STATE.load(deps.storage)?;
+
+    // Games were only played with a native denom
+    let denom = match &msg.legacy_denom {
+        Some(denom) if !denom.is_empty() => denom.clone(),
+        _ => return Err(ContractError::MissingValue { field: "legacy_denom".to_string() }),
+    };
+
+    // ArchID contracts in the migrate msg replace the stored ones
+    // (only stored from 0.1.4)
+    let archid = LEGACY_ARCHID.may_load(deps.storage)?.unwrap_or(Archid { registry: None, cw721: None });
+    let registry = match &msg.archid_registry {
+        Some(registry) => Some(deps.api.addr_validate(registry)?),
+        None => archid.registry,
+    };
+    let cw721 = match &msg.archid_cw721 {
+        Some(cw721) => Some(deps.api.addr_validate(cw721)?),
+        None => archid.cw721,
+    };
+
+    // ArchID ownership becomes an eligibility rule
+    let eligibility = match cw721 {
+        Some(cw721) => EligibilityRule::ArchId { cw721 },
+        None => EligibilityRule::None {},
+    };
+
+    let config = Config {
+        asset: AssetInfo::Native { denom },
+        min_deposit: legacy.min_deposit,
+        extensions: legacy.extensions,
+        stale: legacy.stale,
+        reset_length: legacy.reset_length,
+        payout: Default::default(),
+        fee_bps: 0,
+        fee_recipient: None,
+        price_curve: Default::default(),
+        max_remaining: None,
+        max_round_duration: None,
+        funds_policy: Default::default(),
+        config_delay: 0,
+        archid_registry: registry,
+        referral_bps: 0,
+        eligibility,
+        unlock_reward_bps: 0,
+        keeper_bounty: Uint128::zero(),
+    };
+    // 0.1.4 made the instantiator (or unlocker) the leader of a
+    // round without deposits, recognisable by its reset expiration
+    let last_depositor = if legacy.expiration == legacy.last_deposit + legacy.reset_length {
+        None
+    } else {
+        Some(legacy.last_depositor)
+    };
+    let state = RoundState {
+        round: legacy.round,
+        expiration: legacy.expiration,
+        last_deposit: legacy.last_deposit,
+        last_depositor,
+        paused: legacy.paused,
+        // Start of the round wasn't stored, count it from the upgrade
+        round_start: env.block.time.seconds(),
+        deposits: 0,
+        deposited: Uint128::zero(),
+        last_depositor_name: None,
+        carry_over: Uint128::zero(),
+    };
+    // Nothing else was held by the contract
+    let pot = config.asset.query_balance(&deps.querier, &env.contract.address)?;
+
+    OWNER.save(deps.storage, &legacy.owner)?;
+    GAME_COUNT.save(deps.storage, &game_id)?;
+    CONFIGS.save(deps.storage, game_id, &config)?;
+    CURRENT_ROUNDS.save(deps.storage, game_id, &state)?;
+    POTS.save(deps.storage, game_id, &pot)?;
+    LEGACY_STATE.remove(deps.storage);
+    LEGACY_ARCHID.remove(deps.storage);
+    Ok(())
+}
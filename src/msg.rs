@@ -2,51 +2,386 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use cosmwasm_std::{Addr, Uint128};
+use cw20::Cw20ReceiveMsg;
 
+use crate::state::{
+    AssetInfo, Config, EligibilityRule, FundsPolicy, GamePhase, PayoutSplit, PriceCurve, PlayerRoundStats, PlayerStats, Role, RoundRecord,
+};
+
+// Settings of a game, the contract is instantiated with its first game
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
-    pub archid_registry: Option<Addr>,
-    pub archid_cw721: Option<Addr>,
+    pub asset: AssetInfo,
+    // Used to show players by their ArchID
+    pub archid_registry: Option<String>,
+    // Seconds from creation until the first round ends
     pub expiration: u64,
     pub min_deposit: Uint128,
     pub extensions: u64,
     pub stale: u64,
     pub reset_length: u64,
+    pub payout: Option<PayoutSplit>,
+    pub fee_bps: Option<u64>,
+    pub fee_recipient: Option<String>,
+    pub price_curve: Option<PriceCurve>,
+    pub max_remaining: Option<u64>,
+    pub max_round_duration: Option<u64>,
+    pub funds_policy: Option<FundsPolicy>,
+    pub config_delay: Option<u64>,
+    pub referral_bps: Option<u64>,
+    // Share of a stale round's pot paid to whoever unlocks it, only
+    // if the round had deposits, the rest rolls over to the next round
+    pub unlock_reward_bps: Option<u64>,
+    // Flat amount added to the unlock reward, capped by the pot
+    pub keeper_bounty: Option<Uint128>,
+    pub eligibility: Option<EligibilityRule>,
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    Deposit {},
-    Claim {},
-    UnlockStale {},
+    // Referrer is ignored once the player has registered one
+    Deposit {
+        game_id: u64,
+        quantity: Option<u32>,
+        referrer: Option<String>,
+    },
+    Receive(Cw20ReceiveMsg),
+    Claim {
+        game_id: u64,
+    },
+    UnlockStale {
+        game_id: u64,
+    },
+    WithdrawDividends {
+        game_id: u64,
+    },
+    // Can only be set once
+    SetReferrer {
+        game_id: u64,
+        referrer: String,
+    },
+    WithdrawReferralEarnings {
+        game_id: u64,
+    },
     // Admin only
-    Pause {},
-    Unpause {},
+    CreateGame {
+        config: InstantiateMsg,
+    },
+    Pause {
+        game_id: u64,
+    },
+    Unpause {
+        game_id: u64,
+    },
     Configure {
+        game_id: u64,
         msg: ConfigureMsg,
     },
+    CancelConfig {
+        game_id: u64,
+    },
+    WithdrawFees {
+        game_id: u64,
+    },
+    // Adds to the pot of the current round, or a future
+    // one once it starts, without taking part in the game
+    Fund {
+        game_id: u64,
+        round: Option<u64>,
+    },
+    // Refunds the sender's sponsorship of a round
+    // that hasn't started
+    WithdrawSponsorship {
+        game_id: u64,
+        round: u64,
+    },
+    // Recovers funds not owed to any game, the
+    // recipient defaults to the sender
+    Sweep {
+        asset: AssetInfo,
+        recipient: Option<String>,
+    },
+    // Expiry is in seconds from now
+    ProposeOwner {
+        new_owner: String,
+        expiry: Option<u64>,
+    },
+    CancelOwnershipProposal {},
+    RenounceOwnership {},
+    GrantRole {
+        address: String,
+        role: Role,
+    },
+    RevokeRole {
+        address: String,
+        role: Role,
+    },
+    UpdateAllowlist {
+        game_id: u64,
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    // Pending owner only
+    AcceptOwnership {},
+    // Anyone once the config delay has passed
+    ApplyConfig {
+        game_id: u64,
+    },
+}
+
+// Cw20 hook messages
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    // Referrer is ignored once the player has registered one
+    Deposit {
+        game_id: u64,
+        quantity: Option<u32>,
+        referrer: Option<String>,
+    },
+    Fund {
+        game_id: u64,
+        round: Option<u64>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    // ArchID contracts, replacing any stored by 0.1.4
+    pub archid_registry: Option<String>,
+    pub archid_cw721: Option<String>,
+    // Native denom played by 0.1.4 and earlier, required when
+    // upgrading from them
+    pub legacy_denom: Option<String>,
+    // Refuse to migrate unless game play is paused
+    pub require_paused: Option<bool>,
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
-    Game {},
+    Games {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    Config {
+        game_id: u64,
+    },
+    CurrentRound {
+        game_id: u64,
+    },
+    Status {
+        game_id: u64,
+    },
+    Round {
+        game_id: u64,
+        round: u64,
+    },
+    Rounds {
+        game_id: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    Player {
+        game_id: u64,
+        address: String,
+        round: Option<u64>,
+    },
+    Players {
+        game_id: u64,
+        round: Option<u64>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    Dividends {
+        game_id: u64,
+        address: String,
+    },
+    Fees {
+        game_id: u64,
+    },
+    CurrentPrice {
+        game_id: u64,
+    },
+    Ownership {},
+    Roles {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    PendingConfig {
+        game_id: u64,
+    },
+    Referrals {
+        game_id: u64,
+        address: String,
+    },
+    Allowlist {
+        game_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    Surplus {
+        asset: AssetInfo,
+    },
+    // Defaults to the current round
+    Sponsors {
+        game_id: u64,
+        round: Option<u64>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GameEntry {
+    pub game_id: u64,
+    pub config: Config,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GamesResponse {
+    pub games: Vec<GameEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoundsResponse {
+    pub rounds: Vec<RoundRecord>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct ConfigureMsg {
-    pub owner: Option<Addr>,
-    pub archid_registry: Option<Addr>,
-    pub archid_cw721: Option<Addr>,
+    pub archid_registry: Option<String>,
+    // Seconds from when the change applies until the current round ends
     pub expiration: Option<u64>,
     pub min_deposit: Option<Uint128>,
     pub extensions: Option<u64>,
     pub stale: Option<u64>,
     pub reset_length: Option<u64>,
+    pub payout: Option<PayoutSplit>,
+    pub fee_bps: Option<u64>,
+    pub fee_recipient: Option<String>,
+    pub price_curve: Option<PriceCurve>,
+    pub max_remaining: Option<u64>,
+    pub max_round_duration: Option<u64>,
+    pub funds_policy: Option<FundsPolicy>,
+    pub config_delay: Option<u64>,
+    pub referral_bps: Option<u64>,
+    pub unlock_reward_bps: Option<u64>,
+    pub keeper_bounty: Option<Uint128>,
+    pub eligibility: Option<EligibilityRule>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReferralsResponse {
+    pub address: Addr,
+    // Registered referrer of this address
+    pub referrer: Option<Addr>,
+    // Earned by referring other players
+    pub pending: Uint128,
+    pub withdrawn: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PlayerResponse {
+    pub address: Addr,
+    pub round: u64,
+    pub round_stats: PlayerRoundStats,
+    pub lifetime_stats: PlayerStats,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PlayerRoundEntry {
+    pub address: Addr,
+    pub stats: PlayerRoundStats,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PlayersResponse {
+    pub round: u64,
+    pub players: Vec<PlayerRoundEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SponsorEntry {
+    pub address: Addr,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SponsorsResponse {
+    pub round: u64,
+    pub total: Uint128,
+    pub sponsors: Vec<SponsorEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DividendsResponse {
+    pub address: Addr,
+    pub pending: Uint128,
+    pub withdrawn: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeesResponse {
+    pub fee_bps: u64,
+    pub fee_recipient: Option<Addr>,
+    pub accrued: Uint128,
+    pub paid: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PriceResponse {
+    pub asset: AssetInfo,
+    pub price: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OwnershipResponse {
+    pub owner: Option<Addr>,
+    pub pending_owner: Option<Addr>,
+    pub pending_expiry: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoleMember {
+    pub address: Addr,
+    pub roles: Vec<Role>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RolesResponse {
+    pub owner: Option<Addr>,
+    pub members: Vec<RoleMember>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AllowlistResponse {
+    pub addresses: Vec<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SurplusResponse {
+    pub asset: AssetInfo,
+    pub balance: Uint128,
+    pub accounted: Uint128,
+    pub surplus: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StatusResponse {
+    pub round: u64,
+    pub phase: GamePhase,
+    pub expiration: u64,
+    pub seconds_remaining: u64,
+    pub stale_at: u64,
+    pub pot: Uint128,
+    pub carry_over: Uint128,
+    // Price of the next deposit, None once it overflows
+    pub price: Option<Uint128>,
+    pub leader: Option<Addr>,
+    pub leader_name: Option<String>,
 }
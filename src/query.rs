@@ -1,7 +1,306 @@
-use cosmwasm_std::{Deps, StdResult};
-use crate::state::{State, STATE};
+use cosmwasm_std::{Deps, Env, Order, StdResult};
+use cw_storage_plus::Bound;
 
-pub fn query_game(deps: Deps) -> StdResult<State> {
-    let gamestate: State = STATE.load(deps.storage)?;
-    Ok(gamestate)
-}
\ No newline at end of file
+use crate::msg::{
+    AllowlistResponse, DividendsResponse, FeesResponse, GameEntry, GamesResponse, OwnershipResponse, PlayerResponse,
+    PriceResponse, PlayerRoundEntry, PlayersResponse, ReferralsResponse, RoleMember, RolesResponse, RoundsResponse,
+    SponsorEntry, SponsorsResponse, StatusResponse, SurplusResponse,
+};
+use crate::state::{
+    accounted_balance, ALLOWLIST, AssetInfo, Config, CONFIGS, CURRENT_ROUNDS, FEES, OWNER, pending_dividends, PendingConfig, PENDING_CONFIGS,
+    PENDING_OWNER, PLAYER_ROUNDS, PLAYERS, POTS, REFERRAL_EARNINGS, REFERRERS, ROLES, ROUND_PLAYERS, RoundRecord,
+    ROUNDS, RoundState, SPONSORED, SPONSORS,
+};
+
+// Pagination limits
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+pub fn query_games(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<GamesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let games = CONFIGS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(game_id, config)| GameEntry { game_id, config }))
+        .collect::<StdResult<Vec<GameEntry>>>()?;
+
+    Ok(GamesResponse { games })
+}
+
+pub fn query_config(deps: Deps, game_id: u64) -> StdResult<Config> {
+    let config: Config = CONFIGS.load(deps.storage, game_id)?;
+    Ok(config)
+}
+
+pub fn query_current_round(deps: Deps, game_id: u64) -> StdResult<RoundState> {
+    let state: RoundState = CURRENT_ROUNDS.load(deps.storage, game_id)?;
+    Ok(state)
+}
+
+pub fn query_status(deps: Deps, env: Env, game_id: u64) -> StdResult<StatusResponse> {
+    let config: Config = CONFIGS.load(deps.storage, game_id)?;
+    let state: RoundState = CURRENT_ROUNDS.load(deps.storage, game_id)?;
+    let pot = POTS.may_load(deps.storage, game_id)?.unwrap_or_default();
+
+    Ok(StatusResponse {
+        round: state.round,
+        phase: state.phase(&env.block, &config),
+        expiration: state.expiration,
+        seconds_remaining: state.expiration.saturating_sub(env.block.time.seconds()),
+        stale_at: state.stale_at(&config),
+        pot,
+        carry_over: state.carry_over,
+        price: config.current_price(&state).ok(),
+        leader: state.last_depositor,
+        leader_name: state.last_depositor_name,
+    })
+}
+
+pub fn query_round(deps: Deps, game_id: u64, round: u64) -> StdResult<RoundRecord> {
+    let record: RoundRecord = ROUNDS.load(deps.storage, (game_id, round))?;
+    Ok(record)
+}
+
+pub fn query_rounds(
+    deps: Deps,
+    game_id: u64,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<RoundsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let rounds = ROUNDS
+        .prefix(game_id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, record)| record))
+        .collect::<StdResult<Vec<RoundRecord>>>()?;
+
+    Ok(RoundsResponse { rounds })
+}
+
+// Defaults to the current round
+pub fn query_player(
+    deps: Deps,
+    game_id: u64,
+    address: String,
+    round: Option<u64>,
+) -> StdResult<PlayerResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let round = match round {
+        Some(round) => round,
+        None => CURRENT_ROUNDS.load(deps.storage, game_id)?.round,
+    };
+    let round_stats = PLAYER_ROUNDS
+        .may_load(deps.storage, (game_id, &address, round))?
+        .unwrap_or_default();
+    let lifetime_stats = PLAYERS
+        .may_load(deps.storage, (game_id, &address))?
+        .unwrap_or_default();
+
+    Ok(PlayerResponse {
+        address,
+        round,
+        round_stats,
+        lifetime_stats,
+    })
+}
+
+// Defaults to the current round
+pub fn query_sponsors(
+    deps: Deps,
+    game_id: u64,
+    round: Option<u64>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<SponsorsResponse> {
+    let round = match round {
+        Some(round) => round,
+        None => CURRENT_ROUNDS.load(deps.storage, game_id)?.round,
+    };
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_addr = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let start = start_addr.as_ref().map(Bound::exclusive);
+
+    let sponsors = SPONSORS
+        .prefix((game_id, round))
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(address, amount)| SponsorEntry { address, amount }))
+        .collect::<StdResult<Vec<SponsorEntry>>>()?;
+
+    Ok(SponsorsResponse {
+        round,
+        total: SPONSORED.may_load(deps.storage, (game_id, round))?.unwrap_or_default(),
+        sponsors,
+    })
+}
+
+// Defaults to the current round
+pub fn query_players(
+    deps: Deps,
+    game_id: u64,
+    round: Option<u64>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<PlayersResponse> {
+    let round = match round {
+        Some(round) => round,
+        None => CURRENT_ROUNDS.load(deps.storage, game_id)?.round,
+    };
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_addr = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let start = start_addr.as_ref().map(Bound::exclusive);
+
+    let players = ROUND_PLAYERS
+        .prefix((game_id, round))
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|address| {
+            let address = address?;
+            let stats = PLAYER_ROUNDS.load(deps.storage, (game_id, &address, round))?;
+            Ok(PlayerRoundEntry { address, stats })
+        })
+        .collect::<StdResult<Vec<PlayerRoundEntry>>>()?;
+
+    Ok(PlayersResponse { round, players })
+}
+
+pub fn query_dividends(deps: Deps, game_id: u64, address: String) -> StdResult<DividendsResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let player = PLAYERS
+        .may_load(deps.storage, (game_id, &address))?
+        .unwrap_or_default();
+    let (pending, _) = pending_dividends(deps.storage, game_id, &address, &player)?;
+
+    Ok(DividendsResponse {
+        address,
+        pending,
+        withdrawn: player.dividends_withdrawn,
+    })
+}
+
+pub fn query_referrals(deps: Deps, game_id: u64, address: String) -> StdResult<ReferralsResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let referrer = REFERRERS.may_load(deps.storage, (game_id, &address))?;
+    let pending = REFERRAL_EARNINGS
+        .may_load(deps.storage, (game_id, &address))?
+        .unwrap_or_default();
+    let player = PLAYERS
+        .may_load(deps.storage, (game_id, &address))?
+        .unwrap_or_default();
+
+    Ok(ReferralsResponse {
+        address,
+        referrer,
+        pending,
+        withdrawn: player.referrals_withdrawn,
+    })
+}
+
+pub fn query_fees(deps: Deps, game_id: u64) -> StdResult<FeesResponse> {
+    let config: Config = CONFIGS.load(deps.storage, game_id)?;
+    let fees = FEES.may_load(deps.storage, game_id)?.unwrap_or_default();
+
+    Ok(FeesResponse {
+        fee_bps: config.fee_bps,
+        fee_recipient: config.fee_recipient.or(OWNER.may_load(deps.storage)?),
+        accrued: fees.accrued,
+        paid: fees.paid,
+    })
+}
+
+pub fn query_current_price(deps: Deps, game_id: u64) -> StdResult<PriceResponse> {
+    let config: Config = CONFIGS.load(deps.storage, game_id)?;
+    let state: RoundState = CURRENT_ROUNDS.load(deps.storage, game_id)?;
+    let price = config.current_price(&state)?;
+
+    Ok(PriceResponse {
+        asset: config.asset,
+        price,
+    })
+}
+
+pub fn query_ownership(deps: Deps) -> StdResult<OwnershipResponse> {
+    let pending = PENDING_OWNER.may_load(deps.storage)?;
+
+    Ok(OwnershipResponse {
+        owner: OWNER.may_load(deps.storage)?,
+        pending_owner: pending.as_ref().map(|pending| pending.owner.clone()),
+        pending_expiry: pending.and_then(|pending| pending.expiry),
+    })
+}
+
+pub fn query_roles(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<RolesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_addr = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let start = start_addr.as_ref().map(Bound::exclusive);
+
+    let members = ROLES
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|member| {
+            let (address, roles) = member?;
+            Ok(RoleMember { address, roles })
+        })
+        .collect::<StdResult<Vec<RoleMember>>>()?;
+
+    Ok(RolesResponse {
+        owner: OWNER.may_load(deps.storage)?,
+        members,
+    })
+}
+
+pub fn query_allowlist(
+    deps: Deps,
+    game_id: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AllowlistResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_addr = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let start = start_addr.as_ref().map(Bound::exclusive);
+
+    let addresses = ALLOWLIST
+        .prefix(game_id)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(AllowlistResponse { addresses })
+}
+
+pub fn query_surplus(deps: Deps, env: Env, asset: AssetInfo) -> StdResult<SurplusResponse> {
+    let balance = asset.query_balance(&deps.querier, &env.contract.address)?;
+    let accounted = accounted_balance(deps.storage, &asset)?;
+
+    Ok(SurplusResponse {
+        surplus: balance.saturating_sub(accounted),
+        asset,
+        balance,
+        accounted,
+    })
+}
+
+pub fn query_pending_config(deps: Deps, game_id: u64) -> StdResult<Option<PendingConfig>> {
+    PENDING_CONFIGS.may_load(deps.storage, game_id)
+}
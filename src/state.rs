@@ -1,42 +1,704 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, BlockInfo, Timestamp, Uint128};
-use cw_storage_plus::Item;
+use cosmwasm_std::{
+    Addr, Api, BankMsg, BlockInfo, Coin, CosmosMsg, Decimal, Empty, Order, OverflowError, OverflowOperation, 
+    QuerierWrapper, StdError, StdResult, Storage, Timestamp, to_binary, Uint128, WasmMsg,
+};
+use cw_storage_plus::{Bound, Item, Map};
 use cw_utils::Expiration;
+use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
 
+use archid_registry::msg::{QueryMsg as RegistryQueryMsg, ResolveAddressResponse};
+
+use crate::error::ContractError;
+use crate::msg::ConfigureMsg;
+
+// Game settings, changed by the owner and configurators
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct State {
-    pub owner: Addr,
-    pub expiration: u64,
+pub struct Config {
+    pub asset: AssetInfo,
     pub min_deposit: Uint128,
-    pub last_deposit: u64,
-    pub last_depositor: Addr,
     pub extensions: u64,
     pub stale: u64,
     pub reset_length: u64,
+    pub payout: PayoutSplit,
+    pub fee_bps: u64,
+    pub fee_recipient: Option<Addr>,
+    pub price_curve: PriceCurve,
+    pub max_remaining: Option<u64>,
+    pub max_round_duration: Option<u64>,
+    pub funds_policy: FundsPolicy,
+    pub config_delay: u64,
+    pub archid_registry: Option<Addr>,
+    #[serde(default)]
+    pub referral_bps: u64,
+    #[serde(default)]
+    pub eligibility: EligibilityRule,
+    #[serde(default)]
+    pub unlock_reward_bps: u64,
+    #[serde(default)]
+    pub keeper_bounty: Uint128,
+}
+impl Config {
+    // Price of the next deposit in the current round
+    pub fn current_price(&self, round: &RoundState) -> StdResult<Uint128> {
+        self.price_at(round.deposits)
+    }
+    // Total price of the next `quantity` deposits in the current round
+    pub fn price_for(&self, round: &RoundState, quantity: u64) -> StdResult<Uint128> {
+        let mut total = Uint128::zero();
+        for deposits in round.deposits..round.deposits + quantity {
+            total = total.checked_add(self.price_at(deposits)?)?;
+        }
+        Ok(total)
+    }
+    // Price of a deposit made after `deposits` deposits
+    fn price_at(&self, deposits: u64) -> StdResult<Uint128> {
+        match &self.price_curve {
+            PriceCurve::Flat {} => Ok(self.min_deposit),
+            PriceCurve::Linear { increment } => {
+                let increase = increment.checked_mul(Uint128::from(deposits))?;
+                Ok(self.min_deposit.checked_add(increase)?)
+            }
+            PriceCurve::Exponential { growth_bps } => {
+                let growth = Decimal::from_ratio(
+                    u128::from(BPS_DENOMINATOR) + u128::from(*growth_bps),
+                    BPS_DENOMINATOR,
+                );
+                // Price stops growing after MAX_GROWTH_DEPOSITS deposits
+                let exponent = deposits.min(MAX_GROWTH_DEPOSITS) as u32;
+                let factor = growth.checked_pow(exponent)?;
+                self.min_deposit
+                    .checked_multiply_ratio(factor.atomics(), Decimal::one().atomics())
+                    .map_err(|_| {
+                        StdError::overflow(OverflowError::new(OverflowOperation::Mul, self.min_deposit, factor))
+                    })
+            }
+            PriceCurve::Steps { steps } => {
+                let price = steps
+                    .iter()
+                    .rev()
+                    .find(|step| step.deposits <= deposits)
+                    .map(|step| step.price)
+                    .unwrap_or(self.min_deposit);
+                Ok(price)
+            }
+        }
+    }
+}
+
+// Settings of each game, keyed by game id
+pub const CONFIGS: Map<u64, Config> = Map::new("configs");
+
+// Progress of the current round
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoundState {
     pub round: u64,
+    pub expiration: u64,
+    pub last_deposit: u64,
+    // No one leads a round until its first deposit
+    pub last_depositor: Option<Addr>,
     pub paused: Option<u64>,
+    pub round_start: u64,
+    pub deposits: u64,
+    pub deposited: Uint128,
+    // Primary ArchID of the last depositor
+    #[serde(default)]
+    pub last_depositor_name: Option<String>,
+    // Part of the pot left behind by the previous round
+    #[serde(default)]
+    pub carry_over: Uint128,
 }
-impl State {
+impl RoundState {
     pub fn is_expired(&self, block: &BlockInfo) -> bool {
         Expiration::AtTime(Timestamp::from_seconds(self.expiration)).is_expired(block)
     }
-    pub fn is_stale(&self, block: &BlockInfo) -> bool {       
-        let stale = self.expiration + self.stale;
+    // Rounds without a leader have no prize to wait for
+    // and go stale as soon as they end
+    pub fn stale_at(&self, config: &Config) -> u64 {
+        match self.last_depositor {
+            Some(_) => self.expiration + config.stale,
+            None => self.expiration,
+        }
+    }
+    pub fn is_stale(&self, block: &BlockInfo, config: &Config) -> bool {
+        let stale = self.stale_at(config);
         Expiration::AtTime(Timestamp::from_seconds(stale)).is_expired(block)
     }
     pub fn is_paused(&self) -> bool {
         self.paused.is_some()
     }
+    pub fn phase(&self, block: &BlockInfo, config: &Config) -> GamePhase {
+        if self.is_paused() {
+            GamePhase::Paused
+        } else if !self.is_expired(block) {
+            GamePhase::Active
+        } else if self.is_stale(block, config) {
+            GamePhase::Stale
+        } else {
+            GamePhase::AwaitingClaim
+        }
+    }
+    pub fn is_past_max_duration(&self, block: &BlockInfo, config: &Config) -> bool {
+        match config.max_round_duration {
+            Some(duration) => {
+                let end = self.round_start + duration;
+                Expiration::AtTime(Timestamp::from_seconds(end)).is_expired(block)
+            }
+            None => false,
+        }
+    }
 }
 
-pub const STATE: Item<State> = Item::new("state");
+// Current round of each game, keyed by game id
+pub const CURRENT_ROUNDS: Map<u64, RoundState> = Map::new("current_rounds");
+
+// Settings and current round of a game
+pub fn load_game(storage: &dyn Storage, game_id: u64) -> Result<(Config, RoundState), ContractError> {
+    let config = CONFIGS
+        .may_load(storage, game_id)?
+        .ok_or(ContractError::GameNotFound { game_id })?;
+    let state = CURRENT_ROUNDS.load(storage, game_id)?;
+    Ok((config, state))
+}
+
+// Id of the most recently created game
+pub const GAME_COUNT: Item<u64> = Item::new("game_count");
+
+// Prize pool of each game's current round, keyed by game id
+pub const POTS: Map<u64, Uint128> = Map::new("pots");
 
+// Admin of every game hosted by the contract (removed once renounced)
+pub const OWNER: Item<Addr> = Item::new("owner");
+
+pub fn is_owner(storage: &dyn Storage, address: &Addr) -> StdResult<bool> {
+    Ok(OWNER.may_load(storage)?.as_ref() == Some(address))
+}
+
+// Single item layout of 0.1.4 and earlier, only read when migrating
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyState {
+    pub owner: Addr,
+    pub expiration: u64,
+    pub min_deposit: Uint128,
+    pub last_deposit: u64,
+    pub last_depositor: Addr,
+    pub extensions: u64,
+    pub stale: u64,
+    pub reset_length: u64,
+    pub round: u64,
+    pub paused: Option<u64>,
+}
+
+pub const LEGACY_STATE: Item<LegacyState> = Item::new("state");
+
+// Primary ArchID name of an address, when an ArchID registry is set
+pub fn resolve_archid(
+    querier: &QuerierWrapper,
+    config: &Config,
+    address: &Addr,
+) -> StdResult<Option<String>> {
+    let registry = match &config.archid_registry {
+        Some(registry) => registry,
+        None => return Ok(None),
+    };
+    let query_msg = RegistryQueryMsg::ResolveAddress {
+        address: address.clone(),
+    };
+    let res: ResolveAddressResponse = querier.query_wasm_smart(registry, &query_msg)?;
+    Ok(res.names.and_then(|names| names.into_iter().next()))
+}
+
+// Game phase as seen at a given block
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GamePhase {
+    // Accepting deposits
+    Active,
+    // Timer ran out, last depositor can claim
+    AwaitingClaim,
+    // Prize went unclaimed, anyone can unlock a new round
+    Stale,
+    Paused,
+}
+
+// Longest duration accepted for any game timer (~1 year)
+pub const MAX_DURATION: u64 = 31_536_000;
+
+// Round parameters accepted by instantiate and configure
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct GameConfig {
+    pub min_deposit: Uint128,
+    pub extensions: u64,
+    pub stale: u64,
+    pub reset_length: u64,
+}
+impl GameConfig {
+    pub fn from_config(config: &Config) -> Self {
+        GameConfig {
+            min_deposit: config.min_deposit,
+            extensions: config.extensions,
+            stale: config.stale,
+            reset_length: config.reset_length,
+        }
+    }
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.min_deposit.is_zero() {
+            return Err(ContractError::ZeroValue { field: "min_deposit".to_string() });
+        }
+        validate_duration("extensions", self.extensions)?;
+        validate_duration("stale", self.stale)?;
+        validate_duration("reset_length", self.reset_length)?;
+        Ok(())
+    }
+}
+
+// Durations are in seconds and must be within (0, MAX_DURATION]
+pub fn validate_duration(field: &str, seconds: u64) -> Result<(), ContractError> {
+    if seconds == 0 {
+        return Err(ContractError::ZeroValue { field: field.to_string() });
+    }
+    if seconds > MAX_DURATION {
+        return Err(ContractError::ValueTooLarge { 
+            field: field.to_string(), 
+            max: MAX_DURATION,
+        });
+    }
+    Ok(())
+}
+
+// Optional limits are durations, 0 disables the limit
+pub fn validate_limit(field: &str, seconds: u64) -> Result<Option<u64>, ContractError> {
+    if seconds == 0 {
+        return Ok(None);
+    }
+    validate_duration(field, seconds)?;
+    Ok(Some(seconds))
+}
+
+// Shares are in basis points and must be within [0, BPS_DENOMINATOR]
+pub fn validate_bps(field: &str, bps: u64) -> Result<(), ContractError> {
+    if bps > BPS_DENOMINATOR {
+        return Err(ContractError::ValueTooLarge { 
+            field: field.to_string(), 
+            max: BPS_DENOMINATOR,
+        });
+    }
+    Ok(())
+}
+
+// Ownership transfer waiting to be accepted by the new owner
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingOwner {
+    pub owner: Addr,
+    pub expiry: Option<u64>,
+}
+impl PendingOwner {
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        match self.expiry {
+            Some(expiry) => Expiration::AtTime(Timestamp::from_seconds(expiry)).is_expired(block),
+            None => false,
+        }
+    }
+}
+
+pub const PENDING_OWNER: Item<PendingOwner> = Item::new("pending_owner");
+
+// Configuration change waiting for its delay to pass
+// or for the next round to start
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingConfig {
+    pub config: ConfigureMsg,
+    pub effective_at: u64,
+    pub effective_round: u64,
+}
+impl PendingConfig {
+    pub fn is_effective(&self, block: &BlockInfo, round: u64) -> bool {
+        round >= self.effective_round
+            || Expiration::AtTime(Timestamp::from_seconds(self.effective_at)).is_expired(block)
+    }
+}
+
+// Queued configuration changes, keyed by game id
+pub const PENDING_CONFIGS: Map<u64, PendingConfig> = Map::new("pending_configs");
+
+// Admin permissions (the owner holds every role)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Owner,
+    Pauser,
+    Configurator,
+    FeeManager,
+}
+
+// Roles granted to addresses other than the owner
+pub const ROLES: Map<&Addr, Vec<Role>> = Map::new("roles");
+
+pub fn has_role(
+    storage: &dyn Storage,
+    address: &Addr,
+    role: Role,
+) -> StdResult<bool> {
+    if is_owner(storage, address)? {
+        return Ok(true);
+    }
+    let roles = ROLES.may_load(storage, address)?.unwrap_or_default();
+    Ok(roles.contains(&role))
+}
+
+// Asset used for deposits and prizes
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetInfo {
+    Native { denom: String },
+    Cw20 { address: Addr },
+}
+impl AssetInfo {
+    // Amount of this asset among the native funds sent with a message
+    pub fn sent_amount(&self, funds: &[Coin]) -> Uint128 {
+        match self {
+            AssetInfo::Native { denom } => funds
+                .iter()
+                .filter(|coin| coin.denom == *denom)
+                .map(|coin| coin.amount)
+                .sum(),
+            AssetInfo::Cw20 { .. } => Uint128::zero(),
+        }
+    }
+    pub fn query_balance(&self, querier: &QuerierWrapper, address: &Addr) -> StdResult<Uint128> {
+        match self {
+            AssetInfo::Native { denom } => {
+                Ok(querier.query_balance(address, denom)?.amount)
+            }
+            AssetInfo::Cw20 { address: token } => {
+                let query_msg = Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                };
+                let res: Cw20BalanceResponse = querier.query_wasm_smart(token, &query_msg)?;
+                Ok(res.balance)
+            }
+        }
+    }
+    pub fn transfer_msg(&self, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+        match self {
+            AssetInfo::Native { denom } => {
+                let bank_transfer_msg = BankMsg::Send {
+                    to_address: recipient.to_string(),
+                    amount: vec![Coin {
+                        denom: denom.clone(),
+                        amount,
+                    }],
+                };
+                Ok(CosmosMsg::Bank(bank_transfer_msg))
+            }
+            AssetInfo::Cw20 { address: token } => {
+                let cw20_transfer_msg = Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount,
+                };
+                Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: token.to_string(),
+                    msg: to_binary(&cw20_transfer_msg)?,
+                    funds: vec![],
+                }))
+            }
+        }
+    }
+}
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+// Bounds of exponential prices, so the growth factor
+// (at most 1.1^400, ~3.6e16) always fits a Decimal
+pub const MAX_GROWTH_BPS: u64 = 1_000;
+pub const MAX_GROWTH_DEPOSITS: u64 = 400;
+
+// How the deposit price grows with the number of 
+// deposits made in the current round
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceCurve {
+    Flat {},
+    Linear { increment: Uint128 },
+    Exponential { growth_bps: u64 },
+    Steps { steps: Vec<PriceStep> },
+}
+impl PriceCurve {
+    pub fn validate(&self, min_deposit: Uint128) -> Result<(), ContractError> {
+        match self {
+            // Growth is at most 10% per deposit
+            PriceCurve::Exponential { growth_bps } => {
+                if *growth_bps > MAX_GROWTH_BPS {
+                    return Err(ContractError::ValueTooLarge {
+                        field: "growth_bps".to_string(),
+                        max: MAX_GROWTH_BPS,
+                    });
+                }
+                Ok(())
+            }
+            // Steps must be ordered by deposit count, and can't
+            // be priced below the minimum deposit
+            PriceCurve::Steps { steps } => {
+                if !steps.windows(2).all(|pair| pair[0].deposits < pair[1].deposits) {
+                    return Err(ContractError::InvalidPriceCurve {});
+                }
+                for (index, step) in steps.iter().enumerate() {
+                    let field = format!("steps[{}].price", index);
+                    if step.price.is_zero() {
+                        return Err(ContractError::ZeroValue { field });
+                    }
+                    if step.price < min_deposit {
+                        return Err(ContractError::ValueTooSmall { field, min: min_deposit });
+                    }
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+impl Default for PriceCurve {
+    fn default() -> Self {
+        PriceCurve::Flat {}
+    }
+}
+
+// Price charged once a round has reached `deposits` deposits
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PriceStep {
+    pub deposits: u64,
+    pub price: Uint128,
+}
+
+// How the prize pool is divided when a round is claimed,
+// in basis points (must add up to BPS_DENOMINATOR)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PayoutSplit {
+    pub winner_bps: u64,
+    pub dividend_bps: u64,
+    pub carry_over_bps: u64,
+}
+impl PayoutSplit {
+    pub fn is_valid(&self) -> bool {
+        self.winner_bps + self.dividend_bps + self.carry_over_bps == BPS_DENOMINATOR
+    }
+}
+impl Default for PayoutSplit {
+    // Winner takes all
+    fn default() -> Self {
+        PayoutSplit {
+            winner_bps: BPS_DENOMINATOR,
+            dividend_bps: 0,
+            carry_over_bps: 0,
+        }
+    }
+}
+
+// What happens to funds sent beyond the price of a deposit
+// (overpayment and coins of other denoms)
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FundsPolicy {
+    Reject,
+    #[default]
+    Refund,
+}
+
+// Who is allowed to deposit, rules can be combined with And / Or
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EligibilityRule {
+    // Anyone can deposit
+    None {},
+    // Must hold a token of the collection
+    Cw721Holder { contract: Addr },
+    // Must hold an unexpired ArchID of the collection
+    ArchId { cw721: Addr },
+    // Must be a member of the cw4 group
+    Cw4Member { group: Addr },
+    // Must be on the allowlist
+    Allowlist {},
+    // Must hold at least `amount` of the cw20 token
+    Cw20MinBalance { token: Addr, amount: Uint128 },
+    // Every rule must pass
+    And { rules: Vec<EligibilityRule> },
+    // At least one rule must pass
+    Or { rules: Vec<EligibilityRule> },
+}
+impl EligibilityRule {
+    // Copy of the rule with every contract address validated
+    pub fn validate(&self, api: &dyn Api) -> StdResult<EligibilityRule> {
+        let rule = match self {
+            EligibilityRule::None {} => EligibilityRule::None {},
+            EligibilityRule::Cw721Holder { contract } => EligibilityRule::Cw721Holder {
+                contract: api.addr_validate(contract.as_str())?,
+            },
+            EligibilityRule::ArchId { cw721 } => EligibilityRule::ArchId {
+                cw721: api.addr_validate(cw721.as_str())?,
+            },
+            EligibilityRule::Cw4Member { group } => EligibilityRule::Cw4Member {
+                group: api.addr_validate(group.as_str())?,
+            },
+            EligibilityRule::Allowlist {} => EligibilityRule::Allowlist {},
+            EligibilityRule::Cw20MinBalance { token, amount } => EligibilityRule::Cw20MinBalance {
+                token: api.addr_validate(token.as_str())?,
+                amount: *amount,
+            },
+            EligibilityRule::And { rules } => EligibilityRule::And {
+                rules: rules.iter().map(|rule| rule.validate(api)).collect::<StdResult<_>>()?,
+            },
+            EligibilityRule::Or { rules } => EligibilityRule::Or {
+                rules: rules.iter().map(|rule| rule.validate(api)).collect::<StdResult<_>>()?,
+            },
+        };
+        Ok(rule)
+    }
+}
+impl Default for EligibilityRule {
+    fn default() -> Self {
+        EligibilityRule::None {}
+    }
+}
+
+// Addresses allowed by the Allowlist rule, keyed by (game, address)
+pub const ALLOWLIST: Map<(u64, &Addr), Empty> = Map::new("game_allowlist");
+
+// Dividends owed to depositors but not yet withdrawn, keyed by game id
+pub const DIVIDEND_RESERVES: Map<u64, Uint128> = Map::new("dividend_reserves");
+
+// Referrer registered by each player, keyed by (game, player)
+pub const REFERRERS: Map<(u64, &Addr), Addr> = Map::new("game_referrers");
+// Referral earnings not yet withdrawn, keyed by (game, referrer)
+pub const REFERRAL_EARNINGS: Map<(u64, &Addr), Uint128> = Map::new("game_referral_earnings");
+// Referral earnings owed to referrers but not yet withdrawn, keyed by game id
+pub const REFERRAL_RESERVES: Map<u64, Uint128> = Map::new("referral_reserves");
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct Fees {
+    pub accrued: Uint128,
+    pub paid: Uint128,
+}
+
+// Protocol fees taken from deposits, keyed by game id
+pub const FEES: Map<u64, Fees> = Map::new("game_fees");
+
+// Sponsor contributions, keyed by (game, round, sponsor)
+pub const SPONSORS: Map<(u64, u64, &Addr), Uint128> = Map::new("game_round_sponsors");
+// Total sponsored for each round, keyed by (game, round)
+pub const SPONSORED: Map<(u64, u64), Uint128> = Map::new("sponsored_rounds");
+// Sponsor funds held for rounds that haven't started, keyed by game id
+pub const SPONSOR_RESERVES: Map<u64, Uint128> = Map::new("sponsor_reserves");
+
+// How many rounds ahead of the current one can be sponsored
+pub const MAX_FUND_AHEAD: u64 = 10;
+
+// ArchID contracts stored before they were folded into
+// the config, only read when migrating
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Archid {
     pub registry: Option<Addr>,
     pub cw721: Option<Addr>,
 }
 
-pub const ARCHID: Item<Archid> = Item::new("archid");
\ No newline at end of file
+pub const LEGACY_ARCHID: Item<Archid> = Item::new("archid");
+
+// Balance owed by the games played with `asset`, as pots, reserves
+// and accrued fees, anything held above it is surplus
+pub fn accounted_balance(storage: &dyn Storage, asset: &AssetInfo) -> StdResult<Uint128> {
+    let mut accounted = Uint128::zero();
+    for item in CONFIGS.range(storage, None, None, Order::Ascending) {
+        let (game_id, config) = item?;
+        if config.asset != *asset {
+            continue;
+        }
+        accounted += POTS.may_load(storage, game_id)?.unwrap_or_default();
+        accounted += DIVIDEND_RESERVES.may_load(storage, game_id)?.unwrap_or_default();
+        accounted += REFERRAL_RESERVES.may_load(storage, game_id)?.unwrap_or_default();
+        accounted += FEES.may_load(storage, game_id)?.unwrap_or_default().accrued;
+        accounted += SPONSOR_RESERVES.may_load(storage, game_id)?.unwrap_or_default();
+    }
+    Ok(accounted)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundOutcome {
+    Claimed,
+    Stale,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoundRecord {
+    pub round: u64,
+    pub winner: Option<Addr>,
+    #[serde(default)]
+    pub winner_name: Option<String>,
+    pub prize: Uint128,
+    pub start: u64,
+    pub end: u64,
+    pub deposits: u64,
+    pub deposited: Uint128,
+    pub dividends: Uint128,
+    pub outcome: RoundOutcome,
+    // Paid to whoever unlocked a stale round
+    #[serde(default)]
+    pub unlock_reward: Uint128,
+}
+
+// Completed rounds, keyed by (game, round)
+pub const ROUNDS: Map<(u64, u64), RoundRecord> = Map::new("game_rounds");
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct PlayerRoundStats {
+    pub deposits: u64,
+    pub total: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct PlayerStats {
+    pub deposits: u64,
+    pub total: Uint128,
+    pub rounds: u64,
+    pub dividends_round: u64,
+    pub dividends_withdrawn: Uint128,
+    #[serde(default)]
+    pub referrals_withdrawn: Uint128,
+}
+
+// Per round deposit ledger, keyed by (game, player, round)
+pub const PLAYER_ROUNDS: Map<(u64, &Addr, u64), PlayerRoundStats> = Map::new("game_player_rounds");
+// Depositors of each round, keyed by (game, round, player)
+pub const ROUND_PLAYERS: Map<(u64, u64, &Addr), Empty> = Map::new("game_round_players");
+// Lifetime deposit totals, keyed by (game, player)
+pub const PLAYERS: Map<(u64, &Addr), PlayerStats> = Map::new("game_players");
+
+// Dividends owed to a player from the rounds settled since
+// their last withdrawal, along with the last round counted
+pub fn pending_dividends(
+    storage: &dyn Storage,
+    game_id: u64,
+    player: &Addr,
+    stats: &PlayerStats,
+) -> StdResult<(Uint128, u64)> {
+    let start = Some(Bound::exclusive(stats.dividends_round));
+    let mut pending = Uint128::zero();
+    let mut last_round = stats.dividends_round;
+    for item in PLAYER_ROUNDS
+        .prefix((game_id, player))
+        .range(storage, start, None, Order::Ascending)
+    {
+        let (round, round_stats) = item?;
+        // Current round is not settled yet
+        let record = match ROUNDS.may_load(storage, (game_id, round))? {
+            Some(record) => record,
+            None => break,
+        };
+        if !record.dividends.is_zero() {
+            pending += record.dividends.multiply_ratio(round_stats.total, record.deposited);
+        }
+        last_round = round;
+    }
+    Ok((pending, last_round))
+}